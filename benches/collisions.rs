@@ -0,0 +1,87 @@
+//! Steps the simulation with 10,000 asteroids and 1,000 bullets in play and
+//! reports the time taken per fixed step. Run with
+//! `cargo bench --bench collisions`.
+
+use std::time::{Duration, Instant};
+
+use asteroidslib::{
+    headless_app, Asteroid, AsteroidShape, AsteroidSize, AsteroidsConfig, Bullet, Diameter,
+    FeatureFlags, GameRng, Position, ScreenBounds, Spin, Velocity, BULLET_SIZE,
+};
+use bevy::prelude::*;
+
+const ASTEROIDS: usize = 10_000;
+const BULLETS: usize = 1_000;
+const WARMUP_STEPS: usize = 30;
+const STEPS: usize = 300;
+
+fn main() {
+    let config = AsteroidsConfig::headless()
+        .with_window_size(8_000, 8_000)
+        .with_asteroid_count(0)
+        .with_seed(1);
+    let config = config.clone().with_features(FeatureFlags {
+        waves: false,
+        saucers: false,
+        ..config.features
+    });
+    let mut app = headless_app(config);
+    app.update();
+
+    let bounds = *app.world().resource::<ScreenBounds>();
+    let mut rng = GameRng::new(1);
+    let mut times = Vec::with_capacity(STEPS);
+
+    for step in 0..WARMUP_STEPS + STEPS {
+        // Keep the counts steady as bullets expire and rocks are shot.
+        top_up(&mut app, &mut rng, &bounds);
+
+        let start = Instant::now();
+        app.update();
+        if step >= WARMUP_STEPS {
+            times.push(start.elapsed());
+        }
+    }
+
+    times.sort();
+    let mean = times.iter().sum::<Duration>() / times.len() as u32;
+    println!("{ASTEROIDS} asteroids, {BULLETS} bullets, {STEPS} fixed steps");
+    println!("  mean   {mean:?}");
+    println!("  median {:?}", times[times.len() / 2]);
+    println!("  p99    {:?}", times[times.len() * 99 / 100]);
+    println!("  max    {:?}", times[times.len() - 1]);
+}
+
+fn top_up(app: &mut App, rng: &mut GameRng, bounds: &ScreenBounds) {
+    let world = app.world_mut();
+    let asteroids = world.query::<&Asteroid>().iter(world).count();
+    let bullets = world.query::<&Bullet>().iter(world).count();
+
+    for _ in asteroids..ASTEROIDS {
+        let size = AsteroidSize::Small;
+        let shape = AsteroidShape::random(rng);
+        let velocity = rng.random_point(bounds).normalize_or_zero() * 120.0;
+        world.spawn((
+            Asteroid { size },
+            shape,
+            Spin {
+                angle: 0.0,
+                rate: rng.signed_unit(),
+            },
+            Position(rng.random_point(bounds)),
+            Velocity(velocity),
+            Diameter(size.scale()),
+        ));
+    }
+
+    for _ in bullets..BULLETS {
+        let position = rng.random_point(bounds);
+        let velocity = rng.random_point(bounds).normalize_or_zero() * 360.0;
+        world.spawn((
+            Bullet,
+            Position(position),
+            Velocity(velocity),
+            Diameter(BULLET_SIZE),
+        ));
+    }
+}
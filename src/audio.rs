@@ -0,0 +1,607 @@
+use std::f32::consts::TAU;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use bevy::audio::{AddAudioSource, Source, Volume};
+use bevy::prelude::*;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+use crate::components::*;
+use crate::config::{AsteroidsConfig, AudioConfig};
+use crate::hits::AsteroidDestroyed;
+use crate::plugin::AsteroidsSet;
+use crate::rng::GameRng;
+use crate::saucer::{Saucer, SaucerSize};
+use crate::ship::ShipVisualState;
+use crate::state::{GameState, ShipDestroyed};
+use crate::wave::Wave;
+
+/// Every sound is synthesised mono at this rate.
+const SAMPLE_RATE: u32 = 22_050;
+
+/// Plays the game's sound effects and heartbeat, all synthesised at
+/// startup so no audio files need to be loaded. Needs Bevy's `AudioPlugin`
+/// (part of `DefaultPlugins`); leave it out to run the game silent.
+pub struct AsteroidsAudioPlugin;
+
+impl Plugin for AsteroidsAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_audio_source::<Sound>()
+            .init_resource::<Heartbeat>()
+            .add_systems(Startup, load_sounds)
+            .add_systems(
+                Update,
+                (
+                    apply_pending_audio_changes,
+                    (
+                        play_fire,
+                        play_explosions,
+                        attach_thrust_rumble,
+                        attach_saucer_siren,
+                        beat_heartbeat.run_if(in_state(GameState::Playing)),
+                    ),
+                    (update_thrust_rumble, update_loop_volume),
+                )
+                    .chain()
+                    .after(AsteroidsSet::Input),
+            );
+    }
+}
+
+/// A synthesised clip, ready to hand to an `AudioPlayer`.
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct Sound {
+    samples: Arc<[f32]>,
+}
+
+impl Sound {
+    /// Mono samples, from -1 to 1, at 22.05 kHz.
+    pub fn samples(&self) -> &[f32] {
+        &self.samples
+    }
+
+    /// Length in seconds.
+    pub fn duration(&self) -> f32 {
+        self.samples.len() as f32 / SAMPLE_RATE as f32
+    }
+}
+
+impl Decodable for Sound {
+    type DecoderItem = f32;
+    type Decoder = SoundDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        SoundDecoder {
+            samples: self.samples.clone(),
+            next: 0,
+        }
+    }
+}
+
+/// Plays a [`Sound`] back sample by sample.
+pub struct SoundDecoder {
+    samples: Arc<[f32]>,
+    next: usize,
+}
+
+impl Iterator for SoundDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let sample = self.samples.get(self.next).copied();
+        self.next += 1;
+        sample
+    }
+}
+
+impl Source for SoundDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        Some(self.samples.len().saturating_sub(self.next))
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        Some(Duration::from_secs_f32(
+            self.samples.len() as f32 / SAMPLE_RATE as f32,
+        ))
+    }
+}
+
+/// Everything the game can play.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SoundEffect {
+    /// A falling "pew", for the starship's and saucers' shots.
+    Fire,
+    /// Engine rumble, looped while thrusting.
+    Thrust,
+    /// A noise burst, longer and deeper for bigger asteroids.
+    Explosion(AsteroidSize),
+    /// Wailing tone, looped while a saucer is on screen; the small saucer's
+    /// is higher and faster.
+    Siren(SaucerSize),
+    /// One thump of the heartbeat, which alternates low and high notes.
+    Beat { high: bool },
+}
+
+impl SoundEffect {
+    pub fn synthesize(self) -> Sound {
+        let samples = match self {
+            SoundEffect::Fire => fire(),
+            SoundEffect::Thrust => thrust(),
+            SoundEffect::Explosion(size) => explosion(size),
+            SoundEffect::Siren(size) => siren(size),
+            SoundEffect::Beat { high } => beat(high),
+        };
+
+        Sound {
+            samples: samples.into(),
+        }
+    }
+}
+
+/// `seconds` worth of samples of `wave`, called once per sample with the
+/// time from the start.
+fn render(seconds: f32, mut wave: impl FnMut(f32) -> f32) -> Vec<f32> {
+    let count = (seconds * SAMPLE_RATE as f32) as usize;
+
+    (0..count)
+        .map(|i| wave(i as f32 / SAMPLE_RATE as f32).clamp(-1.0, 1.0))
+        .collect()
+}
+
+/// Keeps its phase across frequency changes, so sweeps do not click.
+#[derive(Default)]
+struct Oscillator {
+    phase: f32,
+}
+
+impl Oscillator {
+    fn advance(&mut self, frequency: f32) -> f32 {
+        let phase = self.phase;
+        self.phase = (self.phase + frequency / SAMPLE_RATE as f32).fract();
+        phase
+    }
+
+    fn sine(&mut self, frequency: f32) -> f32 {
+        (self.advance(frequency) * TAU).sin()
+    }
+
+    fn square(&mut self, frequency: f32) -> f32 {
+        if self.advance(frequency) < 0.5 {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+}
+
+/// White noise through a one-pole low-pass. The lower `cutoff`, from 0
+/// to 1, the deeper the rumble; the output is scaled back up so every
+/// cutoff sounds about as loud.
+struct Noise {
+    rng: GameRng,
+    cutoff: f32,
+    level: f32,
+}
+
+impl Noise {
+    fn new(seed: u64, cutoff: f32) -> Self {
+        Self {
+            rng: GameRng::new(seed),
+            cutoff,
+            level: 0.0,
+        }
+    }
+
+    fn sample(&mut self) -> f32 {
+        self.level += (self.rng.signed_unit() - self.level) * self.cutoff;
+        self.level * ((2.0 - self.cutoff) / self.cutoff).sqrt()
+    }
+}
+
+fn fire() -> Vec<f32> {
+    const LENGTH: f32 = 0.18;
+    let mut oscillator = Oscillator::default();
+
+    render(LENGTH, |t| {
+        let progress = t / LENGTH;
+        let frequency = 1400.0 * (1.0 - 0.75 * progress);
+        oscillator.square(frequency) * 0.3 * (1.0 - progress).powi(2)
+    })
+}
+
+fn thrust() -> Vec<f32> {
+    let mut noise = Noise::new(1, 0.05);
+
+    render(1.0, |_| noise.sample() * 0.25)
+}
+
+fn explosion(size: AsteroidSize) -> Vec<f32> {
+    let (length, cutoff) = match size {
+        AsteroidSize::Big => (1.2, 0.04),
+        AsteroidSize::Medium => (0.8, 0.08),
+        AsteroidSize::Small => (0.5, 0.15),
+    };
+    let mut noise = Noise::new(2, cutoff);
+
+    render(length, |t| noise.sample() * 0.6 * (-4.0 * t / length).exp())
+}
+
+fn siren(size: SaucerSize) -> Vec<f32> {
+    // (centre, swing, wails per second); the loop holds whole wails
+    let (centre, swing, rate) = match size {
+        SaucerSize::Large => (450.0, 120.0, 4.0),
+        SaucerSize::Small => (900.0, 200.0, 8.0),
+    };
+    let mut oscillator = Oscillator::default();
+
+    render(0.5, |t| {
+        oscillator.sine(centre + swing * (t * rate * TAU).sin()) * 0.2
+    })
+}
+
+fn beat(high: bool) -> Vec<f32> {
+    const LENGTH: f32 = 0.12;
+    let frequency = if high { 62.0 } else { 55.0 };
+    let mut oscillator = Oscillator::default();
+
+    render(LENGTH, |t| {
+        oscillator.square(frequency) * 0.5 * (1.0 - t / LENGTH)
+    })
+}
+
+/// Volumes in use, starting from [`AudioConfig`] and changed at runtime by
+/// the embedding app or the exported JS setters.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct AudioSettings {
+    pub master: f32,
+    pub sfx: f32,
+    pub music: f32,
+    pub muted: bool,
+}
+
+impl AudioSettings {
+    /// Linear gain for sound effects, taking master volume and mute into
+    /// account.
+    pub fn sfx_gain(&self) -> f32 {
+        self.gain(self.sfx)
+    }
+
+    /// Linear gain for the heartbeat.
+    pub fn music_gain(&self) -> f32 {
+        self.gain(self.music)
+    }
+
+    fn gain(&self, bus: f32) -> f32 {
+        if self.muted {
+            0.0
+        } else {
+            (self.master * bus).clamp(0.0, 1.0)
+        }
+    }
+}
+
+impl From<&AudioConfig> for AudioSettings {
+    fn from(config: &AudioConfig) -> Self {
+        Self {
+            master: config.master_volume,
+            sfx: config.sfx_volume,
+            music: config.music_volume,
+            muted: config.muted,
+        }
+    }
+}
+
+enum AudioChange {
+    Master(f32),
+    Sfx(f32),
+    Music(f32),
+    Muted(bool),
+    ToggleMute,
+}
+
+/// Changes requested from outside the ECS (the wasm API), applied in order
+/// on the next frame.
+static PENDING_AUDIO_CHANGES: Mutex<Vec<AudioChange>> = Mutex::new(Vec::new());
+
+fn request(change: AudioChange) {
+    if let Ok(mut pending) = PENDING_AUDIO_CHANGES.lock() {
+        pending.push(change);
+    }
+}
+
+/// Sets the master volume, from 0 to 1. Exported to JS.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn set_master_volume(volume: f32) {
+    request(AudioChange::Master(volume));
+}
+
+/// Sets the sound effects volume, from 0 to 1. Exported to JS.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn set_sfx_volume(volume: f32) {
+    request(AudioChange::Sfx(volume));
+}
+
+/// Sets the heartbeat volume, from 0 to 1. Exported to JS.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn set_music_volume(volume: f32) {
+    request(AudioChange::Music(volume));
+}
+
+/// Silences all sound, or brings it back. Exported to JS.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn set_muted(muted: bool) {
+    request(AudioChange::Muted(muted));
+}
+
+/// Mutes sound if it is on, and unmutes it if not. Exported to JS.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn toggle_mute() {
+    request(AudioChange::ToggleMute);
+}
+
+/// One handle per [`SoundEffect`], synthesised once at startup.
+#[derive(Resource)]
+struct Sounds {
+    fire: Handle<Sound>,
+    thrust: Handle<Sound>,
+    explosions: [Handle<Sound>; 3],
+    sirens: [Handle<Sound>; 2],
+    beats: [Handle<Sound>; 2],
+}
+
+impl Sounds {
+    fn explosion(&self, size: AsteroidSize) -> Handle<Sound> {
+        match size {
+            AsteroidSize::Big => self.explosions[0].clone(),
+            AsteroidSize::Medium => self.explosions[1].clone(),
+            AsteroidSize::Small => self.explosions[2].clone(),
+        }
+    }
+
+    fn siren(&self, size: SaucerSize) -> Handle<Sound> {
+        match size {
+            SaucerSize::Large => self.sirens[0].clone(),
+            SaucerSize::Small => self.sirens[1].clone(),
+        }
+    }
+}
+
+/// The looping engine rumble, a child of the starship.
+#[derive(Component)]
+struct ThrustRumble;
+
+/// A sound effect that loops until its entity goes, and so must follow
+/// volume changes while it plays.
+#[derive(Component)]
+struct Looping;
+
+/// Counts down to the next heartbeat, alternating low and high notes.
+#[derive(Resource)]
+struct Heartbeat {
+    timer: Timer,
+    high: bool,
+}
+
+impl Default for Heartbeat {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(0.0, TimerMode::Once),
+            high: false,
+        }
+    }
+}
+
+fn load_sounds(
+    mut commands: Commands,
+    mut sounds: ResMut<Assets<Sound>>,
+    config: Res<AsteroidsConfig>,
+) {
+    let mut add = |effect: SoundEffect| sounds.add(effect.synthesize());
+
+    commands.insert_resource(Sounds {
+        fire: add(SoundEffect::Fire),
+        thrust: add(SoundEffect::Thrust),
+        explosions: [AsteroidSize::Big, AsteroidSize::Medium, AsteroidSize::Small]
+            .map(|size| add(SoundEffect::Explosion(size))),
+        sirens: [SaucerSize::Large, SaucerSize::Small].map(|size| add(SoundEffect::Siren(size))),
+        beats: [false, true].map(|high| add(SoundEffect::Beat { high })),
+    });
+    commands.insert_resource(AudioSettings::from(&config.audio));
+}
+
+fn apply_pending_audio_changes(mut settings: ResMut<AudioSettings>) {
+    let Ok(mut pending) = PENDING_AUDIO_CHANGES.lock() else {
+        return;
+    };
+
+    for change in pending.drain(..) {
+        match change {
+            AudioChange::Master(volume) => settings.master = volume,
+            AudioChange::Sfx(volume) => settings.sfx = volume,
+            AudioChange::Music(volume) => settings.music = volume,
+            AudioChange::Muted(muted) => settings.muted = muted,
+            AudioChange::ToggleMute => settings.muted = !settings.muted,
+        }
+    }
+}
+
+fn play_once(commands: &mut Commands, sound: Handle<Sound>, gain: f32) {
+    if gain > 0.0 {
+        commands.spawn((
+            AudioPlayer(sound),
+            PlaybackSettings::DESPAWN.with_volume(Volume::Linear(gain)),
+        ));
+    }
+}
+
+/// One shot per new bullet, whoever fired it.
+fn play_fire(
+    mut commands: Commands,
+    sounds: Res<Sounds>,
+    settings: Res<AudioSettings>,
+    bullets: Query<(), Added<Bullet>>,
+) {
+    for _ in &bullets {
+        play_once(&mut commands, sounds.fire.clone(), settings.sfx_gain());
+    }
+}
+
+/// A bang per asteroid destroyed, and the biggest one for the starship.
+fn play_explosions(
+    mut commands: Commands,
+    mut asteroids: MessageReader<AsteroidDestroyed>,
+    mut starships: MessageReader<ShipDestroyed>,
+    sounds: Res<Sounds>,
+    settings: Res<AudioSettings>,
+) {
+    let sizes = asteroids.read().map(|asteroid| asteroid.size);
+    let ships = starships.read().map(|_| AsteroidSize::Big);
+
+    for size in sizes.chain(ships) {
+        play_once(&mut commands, sounds.explosion(size), settings.sfx_gain());
+    }
+}
+
+/// Gives each new starship its engine rumble, paused until it thrusts.
+fn attach_thrust_rumble(
+    mut commands: Commands,
+    sounds: Res<Sounds>,
+    settings: Res<AudioSettings>,
+    query: Query<Entity, Added<Starship>>,
+) {
+    for entity in &query {
+        commands.entity(entity).with_child((
+            ThrustRumble,
+            Looping,
+            AudioPlayer(sounds.thrust.clone()),
+            PlaybackSettings::LOOP
+                .paused()
+                .with_volume(Volume::Linear(settings.sfx_gain())),
+        ));
+    }
+}
+
+/// Gives each new saucer its siren, which stops when the saucer despawns.
+fn attach_saucer_siren(
+    mut commands: Commands,
+    sounds: Res<Sounds>,
+    settings: Res<AudioSettings>,
+    query: Query<(Entity, &Saucer), Added<Saucer>>,
+) {
+    for (entity, saucer) in &query {
+        commands.entity(entity).with_child((
+            Looping,
+            AudioPlayer(sounds.siren(saucer.size)),
+            PlaybackSettings::LOOP.with_volume(Volume::Linear(settings.sfx_gain())),
+        ));
+    }
+}
+
+/// Runs the rumble while the starship shows its flame.
+fn update_thrust_rumble(
+    starships: Query<&ShipVisualState>,
+    rumbles: Query<(&ChildOf, &AudioSink), With<ThrustRumble>>,
+) {
+    for (child_of, sink) in &rumbles {
+        let thrusting = starships
+            .get(child_of.parent())
+            .is_ok_and(|state| state.flame);
+
+        if thrusting && sink.is_paused() {
+            sink.play();
+        } else if !thrusting && !sink.is_paused() {
+            sink.pause();
+        }
+    }
+}
+
+fn update_loop_volume(
+    settings: Res<AudioSettings>,
+    mut sinks: Query<&mut AudioSink, With<Looping>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    for mut sink in &mut sinks {
+        sink.set_volume(Volume::Linear(settings.sfx_gain()));
+    }
+}
+
+/// How much of the wave is left to shoot, from 1 down to 0. An asteroid
+/// counts for itself and every fragment it will still break into, so the
+/// heartbeat speeds up steadily rather than slowing down after each split.
+fn wave_remaining(
+    config: &AsteroidsConfig,
+    wave: u32,
+    asteroids: impl Iterator<Item = AsteroidSize>,
+) -> f32 {
+    let shots = |size: AsteroidSize| {
+        let mut shots = 1;
+        let mut pieces = 1;
+        let mut size = size;
+
+        while let Some(fragment) = size.split() {
+            pieces *= config.splits.fragments(size);
+            shots += pieces;
+            size = fragment;
+        }
+
+        shots
+    };
+    let total = config.wave_asteroid_count(wave) * shots(AsteroidSize::Big);
+    let left: usize = asteroids.map(shots).sum();
+
+    if total == 0 {
+        0.0
+    } else {
+        left as f32 / total as f32
+    }
+}
+
+/// The two-note heartbeat, quickening as the field empties. It rests while
+/// no asteroids are left.
+#[allow(clippy::too_many_arguments)]
+fn beat_heartbeat(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut heartbeat: ResMut<Heartbeat>,
+    sounds: Res<Sounds>,
+    settings: Res<AudioSettings>,
+    config: Res<AsteroidsConfig>,
+    wave: Res<Wave>,
+    asteroids: Query<&Asteroid>,
+) {
+    if asteroids.is_empty() {
+        return;
+    }
+
+    let remaining = wave_remaining(
+        &config,
+        wave.number,
+        asteroids.iter().map(|asteroid| asteroid.size),
+    );
+    let interval = config.audio.beat_interval(remaining);
+    heartbeat
+        .timer
+        .set_duration(Duration::from_secs_f32(interval));
+
+    if !heartbeat.timer.tick(time.delta()).is_finished() {
+        return;
+    }
+
+    let note = sounds.beats[usize::from(heartbeat.high)].clone();
+    play_once(&mut commands, note, settings.music_gain());
+    heartbeat.high = !heartbeat.high;
+    heartbeat.timer.reset();
+}
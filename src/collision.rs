@@ -0,0 +1,223 @@
+use bevy::prelude::*;
+
+use crate::components::*;
+use crate::systems::ScreenBounds;
+
+/// A convex piece of a [`Collider`], in world space. Pieces are at most
+/// triangles, kept in fixed-size arrays so colliders for thousands of
+/// asteroids can be rebuilt every step without allocating per piece.
+#[derive(Clone, Copy, Debug)]
+enum Convex {
+    /// A triangle, or a segment when `len` is 2.
+    Polygon {
+        vertices: [Vec2; 3],
+        len: usize,
+    },
+    Circle {
+        centre: Vec2,
+        radius: f32,
+    },
+}
+
+impl Convex {
+    fn segment(start: Vec2, end: Vec2) -> Self {
+        Convex::Polygon {
+            vertices: [start, end, end],
+            len: 2,
+        }
+    }
+
+    fn triangle(vertices: [Vec2; 3]) -> Self {
+        Convex::Polygon { vertices, len: 3 }
+    }
+
+    /// The extent of this piece along `axis`.
+    fn project(&self, axis: Vec2) -> (f32, f32) {
+        match self {
+            Convex::Polygon { vertices, len } => vertices[..*len]
+                .iter()
+                .map(|vertex| vertex.dot(axis))
+                .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), d| {
+                    (min.min(d), max.max(d))
+                }),
+            Convex::Circle { centre, radius } => {
+                let d = centre.dot(axis);
+                (d - radius, d + radius)
+            }
+        }
+    }
+
+    /// Candidate separating axes contributed by this piece against `other`,
+    /// and how many of them there are.
+    fn axes(&self, other: &Convex) -> ([Vec2; 3], usize) {
+        let mut axes = [Vec2::ZERO; 3];
+
+        match (self, other) {
+            (Convex::Polygon { vertices, len }, _) => {
+                for (i, axis) in axes.iter_mut().enumerate().take(*len) {
+                    *axis = (vertices[(i + 1) % len] - vertices[i])
+                        .perp()
+                        .normalize_or_zero();
+                }
+                (axes, *len)
+            }
+            (Convex::Circle { centre, .. }, Convex::Polygon { vertices, len }) => {
+                let closest = vertices[..*len]
+                    .iter()
+                    .min_by(|a, b| {
+                        a.distance_squared(*centre)
+                            .total_cmp(&b.distance_squared(*centre))
+                    })
+                    .copied()
+                    .unwrap_or(*centre);
+                axes[0] = (closest - *centre).normalize_or_zero();
+                (axes, 1)
+            }
+            (Convex::Circle { centre, .. }, Convex::Circle { centre: other, .. }) => {
+                axes[0] = (*other - *centre).normalize_or_zero();
+                (axes, 1)
+            }
+        }
+    }
+
+    /// Separating axis test.
+    fn overlaps(&self, other: &Convex) -> bool {
+        let (own, own_len) = self.axes(other);
+        let (others, others_len) = other.axes(self);
+
+        own[..own_len]
+            .iter()
+            .chain(&others[..others_len])
+            .filter(|axis| **axis != Vec2::ZERO)
+            .all(|axis| {
+                let (min_a, max_a) = self.project(*axis);
+                let (min_b, max_b) = other.project(*axis);
+                min_a <= max_b && min_b <= max_a
+            })
+    }
+
+    fn translated(&self, offset: Vec2) -> Self {
+        match *self {
+            Convex::Polygon { vertices, len } => Convex::Polygon {
+                vertices: vertices.map(|vertex| vertex + offset),
+                len,
+            },
+            Convex::Circle { centre, radius } => Convex::Circle {
+                centre: centre + offset,
+                radius,
+            },
+        }
+    }
+}
+
+/// The outline of an entity in world space, as a union of convex pieces,
+/// for exact overlap tests between ships, bullets, saucers and asteroids.
+#[derive(Clone, Debug)]
+pub struct Collider {
+    parts: Vec<Convex>,
+    /// Bounding circle, checked before the exact test.
+    centre: Vec2,
+    radius: f32,
+}
+
+impl Collider {
+    pub fn circle(centre: Vec2, radius: f32) -> Self {
+        Self {
+            parts: vec![Convex::Circle { centre, radius }],
+            centre,
+            radius,
+        }
+    }
+
+    /// The path a point covers between two steps, so a fast bullet cannot
+    /// pass through something thinner than one step of travel.
+    pub fn segment(start: Vec2, end: Vec2) -> Self {
+        Self {
+            parts: vec![Convex::segment(start, end)],
+            centre: start.midpoint(end),
+            radius: start.distance(end) / 2.0,
+        }
+    }
+
+    /// A bullet as the line it travelled over the last step.
+    pub fn bullet(position: &Position, velocity: &Velocity, dt: f32) -> Self {
+        Self::segment(position.0 - velocity.0 * dt, position.0)
+    }
+
+    /// The starship's triangle, as drawn.
+    pub fn starship(starship: &Starship, position: &Position, diameter: &Diameter) -> Self {
+        let rotation = Vec2::from_angle(starship.rotation_angle);
+        let outline =
+            STARSHIP_OUTLINE.map(|vertex| position.0 + rotation.rotate(vertex * diameter.0));
+
+        Self {
+            parts: vec![Convex::triangle(outline)],
+            centre: position.0,
+            radius: bounding_radius(position.0, &outline),
+        }
+    }
+
+    /// An asteroid's outline, split into a fan of triangles from its centre
+    /// so each piece is convex however jagged the rock is.
+    pub fn asteroid(
+        shape: &AsteroidShape,
+        spin: &Spin,
+        position: &Position,
+        diameter: &Diameter,
+    ) -> Self {
+        let rotation = Vec2::from_angle(spin.angle);
+        let outline: Vec<Vec2> = shape
+            .0
+            .iter()
+            .map(|vertex| position.0 + rotation.rotate(*vertex * diameter.0))
+            .collect();
+        let parts = outline
+            .iter()
+            .zip(outline.iter().cycle().skip(1))
+            .map(|(a, b)| Convex::triangle([position.0, *a, *b]))
+            .collect();
+
+        Self {
+            parts,
+            centre: position.0,
+            radius: bounding_radius(position.0, &outline),
+        }
+    }
+
+    /// Like [`overlaps`](Self::overlaps), but on the wrapping playfield: an
+    /// entity straddling one edge can hit something at the opposite edge.
+    pub fn overlaps_wrapped(&self, other: &Collider, bounds: &ScreenBounds) -> bool {
+        let nearest = self.centre + bounds.wrapped_delta(self.centre, other.centre);
+        self.overlaps_shifted(other, nearest - other.centre)
+    }
+
+    /// Centre and radius of a circle enclosing the whole collider.
+    pub fn bounding_circle(&self) -> (Vec2, f32) {
+        (self.centre, self.radius)
+    }
+
+    pub fn overlaps(&self, other: &Collider) -> bool {
+        self.overlaps_shifted(other, Vec2::ZERO)
+    }
+
+    /// Tests against `other` moved by `shift`.
+    fn overlaps_shifted(&self, other: &Collider, shift: Vec2) -> bool {
+        if self.centre.distance(other.centre + shift) > self.radius + other.radius {
+            return false;
+        }
+
+        self.parts.iter().any(|part| {
+            other
+                .parts
+                .iter()
+                .any(|other| part.overlaps(&other.translated(shift)))
+        })
+    }
+}
+
+fn bounding_radius(centre: Vec2, outline: &[Vec2]) -> f32 {
+    outline
+        .iter()
+        .map(|vertex| vertex.distance(centre))
+        .fold(0.0, f32::max)
+}
@@ -0,0 +1,164 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+
+use crate::rng::GameRng;
+use crate::ship::ShipVisualState;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsteroidSize {
+    Big,
+    Medium,
+    Small,
+}
+
+impl AsteroidSize {
+    pub fn scale(&self) -> f32 {
+        match self {
+            AsteroidSize::Big => 100.0,
+            AsteroidSize::Medium => 65.0,
+            AsteroidSize::Small => 30.0,
+        }
+    }
+
+    /// Points for shooting an asteroid of this size.
+    pub fn points(&self) -> u32 {
+        match self {
+            AsteroidSize::Big => 20,
+            AsteroidSize::Medium => 50,
+            AsteroidSize::Small => 100,
+        }
+    }
+
+    /// The size of the fragments this asteroid breaks into, if any.
+    pub fn split(&self) -> Option<AsteroidSize> {
+        match self {
+            AsteroidSize::Big => Some(AsteroidSize::Medium),
+            AsteroidSize::Medium => Some(AsteroidSize::Small),
+            AsteroidSize::Small => None,
+        }
+    }
+}
+
+pub const STARSHIP_SIZE: f32 = 50.0;
+pub const BULLET_SIZE: f32 = 5.0;
+
+/// The starship's triangle, nose up, in units of its [`Diameter`].
+pub const STARSHIP_OUTLINE: [Vec2; 3] = [
+    Vec2::new(0.0, 0.5),
+    Vec2::new(-0.25, -0.5),
+    Vec2::new(0.25, -0.5),
+];
+
+#[derive(Component)]
+#[require(Gun, Thrusting, ShipVisualState)]
+pub struct Starship {
+    pub rotation_angle: f32,
+}
+
+impl Starship {
+    pub fn direction(&self) -> Vec2 {
+        let (y, x) = (self.rotation_angle + PI / 2.0).sin_cos();
+
+        Vec2::new(x, y)
+    }
+
+    /// The tip of the starship's outline, where its bullets come from.
+    pub fn nose(&self, position: &Position, diameter: &Diameter) -> Vec2 {
+        position.0 + Vec2::from_angle(self.rotation_angle).rotate(STARSHIP_OUTLINE[0] * diameter.0)
+    }
+}
+
+#[derive(Component)]
+#[require(Travelled)]
+pub struct Bullet;
+
+/// How far, and for how long, a bullet has flown, measured along its path
+/// so wrapping around the screen does not reset it.
+#[derive(Component, Default, Debug)]
+pub struct Travelled {
+    pub distance: f32,
+    pub seconds: f32,
+}
+
+/// Whether the starship's engine fired this step.
+#[derive(Component, Default, Debug)]
+pub struct Thrusting(pub bool);
+
+/// The starship's firing state.
+#[derive(Component, Debug)]
+pub struct Gun {
+    /// Seconds since the last shot.
+    pub since_last_shot: f32,
+}
+
+impl Default for Gun {
+    fn default() -> Self {
+        Self {
+            since_last_shot: f32::INFINITY,
+        }
+    }
+}
+
+#[derive(Component)]
+#[require(AsteroidShape, Spin)]
+pub struct Asteroid {
+    pub size: AsteroidSize,
+}
+
+/// Outline of an asteroid, counter-clockwise around its centre and in
+/// units of its [`Diameter`], so every vertex lies within 0.5 of the origin.
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct AsteroidShape(pub Vec<Vec2>);
+
+impl AsteroidShape {
+    /// A regular polygon, for asteroids spawned without a random shape.
+    pub fn regular(sides: usize) -> Self {
+        Self(
+            (0..sides)
+                .map(|i| Vec2::from_angle(i as f32 * 2.0 * PI / sides as f32) * 0.5)
+                .collect(),
+        )
+    }
+
+    /// A jagged rock with a random number of vertices, each pulled in from
+    /// the bounding circle by a random amount.
+    pub fn random(rng: &mut GameRng) -> Self {
+        let sides = rng.range(9.0, 14.0) as usize;
+        let step = 2.0 * PI / sides as f32;
+
+        Self(
+            (0..sides)
+                .map(|i| {
+                    let angle = (i as f32 + rng.signed_unit() * 0.3) * step;
+                    Vec2::from_angle(angle) * rng.range(0.35, 0.5)
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Default for AsteroidShape {
+    fn default() -> Self {
+        Self::regular(12)
+    }
+}
+
+/// Rotation of an asteroid about its centre, in radians, and how fast it
+/// turns in radians per second.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct Spin {
+    pub angle: f32,
+    pub rate: f32,
+}
+
+#[derive(Component)]
+pub struct Position(pub Vec2);
+
+#[derive(Component)]
+pub struct Velocity(pub Vec2);
+
+/// Bounding size of an entity in world units, used for collisions and for
+/// deciding when it has fully left the screen.
+#[derive(Component, Clone, Copy)]
+pub struct Diameter(pub f32);
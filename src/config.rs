@@ -0,0 +1,716 @@
+use std::f32::consts::PI;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::window::WindowResolution;
+use serde::{Deserialize, Serialize};
+
+use crate::components::AsteroidSize;
+use crate::input::InputBindings;
+
+const VIEWPORT_WIDTH: u32 = 1280;
+const VIEWPORT_HEIGHT: u32 = 720;
+const ASTEROID_COUNT: usize = 6;
+const TIMESTEP_HZ: f64 = 60.0;
+const ASTEROID_VELOCITY: f32 = 120.0;
+const BULLET_VELOCITY: f32 = 360.0;
+const BULLET_DISTANCE: f32 = VIEWPORT_HEIGHT as f32 * 0.8;
+const MAX_BULLETS: usize = 4;
+const FIRE_COOLDOWN: f32 = 0.1;
+const AUTO_FIRE_INTERVAL: f32 = 0.25;
+const STARSHIP_ROTATION_SPEED: f32 = 300.0 * 2.0 * PI / 360.0;
+const STARSHIP_ACCELERATION: f32 = 720.0;
+// Same drag as losing 1% of speed every frame at 60 Hz.
+const STARSHIP_DECELERATION: f32 = 0.603;
+const STARSHIP_MAX_VELOCITY: f32 = 600.0;
+const ASTEROID_MAX_SPIN: f32 = 0.8;
+const COLLISION_CELL_SIZE: f32 = 100.0;
+const STARTING_LIVES: u32 = 3;
+const RESPAWN_DELAY: f32 = 2.0;
+const RESPAWN_CLEAR_RADIUS: f32 = 150.0;
+const GAME_OVER_DELAY: f32 = 4.0;
+const RESPAWN_INVULNERABILITY: f32 = 3.0;
+const BLINK_INTERVAL: f32 = 0.15;
+const EXTRA_LIFE_EVERY: u32 = 10_000;
+const EXTRA_ASTEROIDS_PER_WAVE: usize = 2;
+const MAX_ASTEROIDS_PER_WAVE: usize = 12;
+const SPEED_INCREASE_PER_WAVE: f32 = 0.1;
+const WAVE_INTERSTITIAL: f32 = 2.0;
+const SAUCER_SPAWN_INTERVAL: f32 = 15.0;
+const LARGE_SAUCER_SPEED: f32 = 150.0;
+const SMALL_SAUCER_SPEED: f32 = 220.0;
+const SAUCER_TURN_INTERVAL: f32 = 1.0;
+const SAUCER_FIRE_INTERVAL: f32 = 1.2;
+const SAUCER_BULLET_VELOCITY: f32 = 300.0;
+const SMALL_SAUCER_SCORE: u32 = 10_000;
+const PERFECT_AIM_SCORE: u32 = 40_000;
+const FRAGMENTS_PER_SPLIT: usize = 2;
+const MEDIUM_FRAGMENT_SPREAD: f32 = 0.8;
+const SMALL_FRAGMENT_SPREAD: f32 = 1.2;
+const HYPERSPACE_DURATION: f32 = 0.5;
+const HYPERSPACE_FAILURE_CHANCE: f32 = 0.1;
+const MAX_PARTICLES: usize = 512;
+const EXPLOSION_PARTICLES: usize = 24;
+const EXPLOSION_SPEED: f32 = 160.0;
+const SPARK_LIFETIME: f32 = 0.8;
+const DEBRIS_SPEED: f32 = 60.0;
+const DEBRIS_LIFETIME: f32 = 2.0;
+const EXHAUST_RATE: f32 = 40.0;
+const EXHAUST_SPEED: f32 = 180.0;
+const EXHAUST_LIFETIME: f32 = 0.3;
+const MASTER_VOLUME: f32 = 0.8;
+const SFX_VOLUME: f32 = 1.0;
+const MUSIC_VOLUME: f32 = 0.7;
+const SLOWEST_BEAT: f32 = 1.0;
+const FASTEST_BEAT: f32 = 0.25;
+const STICK_DEAD_ZONE: f32 = 0.2;
+const HIT_RUMBLE_SECONDS: f32 = 0.5;
+const SHOT_RUMBLE_SECONDS: f32 = 0.1;
+const TOUCH_STICK_RADIUS: f32 = 80.0;
+const TOUCH_DEAD_ZONE: f32 = 0.15;
+const TOUCH_THRUST_THRESHOLD: f32 = 0.5;
+const SWIPE_DISTANCE: f32 = 60.0;
+const TOUCH_HOLD_DELAY: f32 = 0.25;
+const AIM_TOLERANCE: f32 = 0.05;
+
+/// Everything that can be tuned on an [`AsteroidsPlugin`](crate::AsteroidsPlugin).
+///
+/// Built with `AsteroidsConfig::default()` and the `with_*` methods, then
+/// inserted as a resource so systems can read it at runtime.
+#[derive(Resource, Clone, Debug, Serialize, Deserialize)]
+pub struct AsteroidsConfig {
+    pub window: WindowConfig,
+    /// Number of big asteroids in the first wave.
+    pub asteroid_count: usize,
+    pub physics: PhysicsConfig,
+    pub rules: RulesConfig,
+    pub waves: WaveConfig,
+    pub saucers: SaucerConfig,
+    pub splits: SplitConfig,
+    pub hyperspace: HyperspaceConfig,
+    pub bullets: BulletConfig,
+    pub particles: ParticleConfig,
+    pub audio: AudioConfig,
+    /// Keys for each [`Action`](crate::Action), until changed in the pause
+    /// menu. Left out of a [`Replay`](crate::Replay), which records the
+    /// actions themselves.
+    #[serde(skip)]
+    pub bindings: InputBindings,
+    pub gamepad: GamepadConfig,
+    pub touch: TouchConfig,
+    pub features: FeatureFlags,
+    /// Seed for [`GameRng`](crate::GameRng); `None` picks one at random.
+    pub seed: Option<u64>,
+}
+
+impl Default for AsteroidsConfig {
+    fn default() -> Self {
+        Self {
+            window: WindowConfig::default(),
+            asteroid_count: ASTEROID_COUNT,
+            physics: PhysicsConfig::default(),
+            rules: RulesConfig::default(),
+            waves: WaveConfig::default(),
+            saucers: SaucerConfig::default(),
+            splits: SplitConfig::default(),
+            hyperspace: HyperspaceConfig::default(),
+            bullets: BulletConfig::default(),
+            particles: ParticleConfig::default(),
+            audio: AudioConfig::default(),
+            bindings: InputBindings::default(),
+            gamepad: GamepadConfig::default(),
+            touch: TouchConfig::default(),
+            features: FeatureFlags::default(),
+            seed: None,
+        }
+    }
+}
+
+impl AsteroidsConfig {
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.window.title = title.into();
+        self
+    }
+
+    pub fn with_window_size(mut self, width: u32, height: u32) -> Self {
+        self.window.width = width;
+        self.window.height = height;
+        self
+    }
+
+    pub fn with_asteroid_count(mut self, count: usize) -> Self {
+        self.asteroid_count = count;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn with_physics(mut self, physics: PhysicsConfig) -> Self {
+        self.physics = physics;
+        self
+    }
+
+    /// Defaults suited to an app without a window: no camera, no window
+    /// tracking, no mobile buttons and straight into play without a title.
+    pub fn headless() -> Self {
+        Self::default().with_features(FeatureFlags {
+            spawn_camera: false,
+            keyboard_input: true,
+            gamepad_input: true,
+            mobile_input: false,
+            touch_input: true,
+            track_window_size: false,
+            title_screen: false,
+            waves: true,
+            saucers: true,
+        })
+    }
+
+    pub fn with_rules(mut self, rules: RulesConfig) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    pub fn with_waves(mut self, waves: WaveConfig) -> Self {
+        self.waves = waves;
+        self
+    }
+
+    pub fn with_saucers(mut self, saucers: SaucerConfig) -> Self {
+        self.saucers = saucers;
+        self
+    }
+
+    pub fn with_splits(mut self, splits: SplitConfig) -> Self {
+        self.splits = splits;
+        self
+    }
+
+    pub fn with_hyperspace(mut self, hyperspace: HyperspaceConfig) -> Self {
+        self.hyperspace = hyperspace;
+        self
+    }
+
+    pub fn with_bullets(mut self, bullets: BulletConfig) -> Self {
+        self.bullets = bullets;
+        self
+    }
+
+    pub fn with_particles(mut self, particles: ParticleConfig) -> Self {
+        self.particles = particles;
+        self
+    }
+
+    pub fn with_audio(mut self, audio: AudioConfig) -> Self {
+        self.audio = audio;
+        self
+    }
+
+    pub fn with_bindings(mut self, bindings: InputBindings) -> Self {
+        self.bindings = bindings;
+        self
+    }
+
+    pub fn with_gamepad(mut self, gamepad: GamepadConfig) -> Self {
+        self.gamepad = gamepad;
+        self
+    }
+
+    pub fn with_touch(mut self, touch: TouchConfig) -> Self {
+        self.touch = touch;
+        self
+    }
+
+    /// Never spawn a new wave, leaving the field as it is once cleared.
+    pub fn without_waves(mut self) -> Self {
+        self.features.waves = false;
+        self
+    }
+
+    /// How many big asteroids wave `wave` (starting at 1) spawns.
+    pub fn wave_asteroid_count(&self, wave: u32) -> usize {
+        let extra = self.waves.extra_asteroids_per_wave * wave.saturating_sub(1) as usize;
+        (self.asteroid_count + extra).min(self.waves.max_asteroids.max(self.asteroid_count))
+    }
+
+    /// Asteroid speed in wave `wave` (starting at 1).
+    pub fn wave_asteroid_velocity(&self, wave: u32) -> f32 {
+        let factor = 1.0 + self.waves.speed_increase_per_wave * wave.saturating_sub(1) as f32;
+        self.physics.asteroid_velocity * factor
+    }
+
+    pub fn with_features(mut self, features: FeatureFlags) -> Self {
+        self.features = features;
+        self
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WindowConfig {
+    pub title: String,
+    pub width: u32,
+    pub height: u32,
+    /// Let Bevy resize the <canvas> to fill its parent on web.
+    pub fit_canvas_to_parent: bool,
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self {
+            title: "Asteroids".into(),
+            width: VIEWPORT_WIDTH,
+            height: VIEWPORT_HEIGHT,
+            fit_canvas_to_parent: true,
+        }
+    }
+}
+
+impl WindowConfig {
+    /// The primary window described by this config.
+    pub fn window(&self) -> Window {
+        Window {
+            fit_canvas_to_parent: self.fit_canvas_to_parent,
+            resolution: WindowResolution::new(self.width, self.height),
+            title: self.title.clone(),
+            ..default()
+        }
+    }
+}
+
+/// Movement constants, in world units and radians per second.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct PhysicsConfig {
+    /// Rate of the fixed simulation step that all movement runs on.
+    pub timestep_hz: f64,
+    pub asteroid_velocity: f32,
+    /// Fastest an asteroid may spin; each one picks a rate up to this.
+    pub asteroid_max_spin: f32,
+    pub bullet_velocity: f32,
+    pub starship_rotation_speed: f32,
+    pub starship_acceleration: f32,
+    /// Exponential drag rate applied while not thrusting: speed is scaled
+    /// by `exp(-starship_deceleration * dt)` each step.
+    pub starship_deceleration: f32,
+    pub starship_max_velocity: f32,
+    /// Side of the cells asteroids are bucketed into for collision checks.
+    /// About the size of a big asteroid works well.
+    pub collision_cell_size: f32,
+}
+
+impl PhysicsConfig {
+    pub fn timestep(&self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.timestep_hz)
+    }
+}
+
+impl Default for PhysicsConfig {
+    fn default() -> Self {
+        Self {
+            timestep_hz: TIMESTEP_HZ,
+            asteroid_velocity: ASTEROID_VELOCITY,
+            asteroid_max_spin: ASTEROID_MAX_SPIN,
+            bullet_velocity: BULLET_VELOCITY,
+            starship_rotation_speed: STARSHIP_ROTATION_SPEED,
+            starship_acceleration: STARSHIP_ACCELERATION,
+            starship_deceleration: STARSHIP_DECELERATION,
+            starship_max_velocity: STARSHIP_MAX_VELOCITY,
+            collision_cell_size: COLLISION_CELL_SIZE,
+        }
+    }
+}
+
+/// Lives, scoring and the pacing of the game flow. Times are in seconds.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct RulesConfig {
+    pub starting_lives: u32,
+    /// Minimum time between losing a ship and the next one appearing.
+    pub respawn_delay: f32,
+    /// The next ship only appears once no asteroid is this close to the centre.
+    pub respawn_clear_radius: f32,
+    /// How long the game-over screen stays up before the title returns.
+    pub game_over_delay: f32,
+    /// How long a respawned starship cannot be hit; `0` disables it.
+    pub respawn_invulnerability: f32,
+    /// The invulnerable starship blinks off and on every this many seconds.
+    pub blink_interval: f32,
+    /// Award an extra life each time the score passes a multiple of this;
+    /// `0` disables extra lives.
+    pub extra_life_every: u32,
+}
+
+impl Default for RulesConfig {
+    fn default() -> Self {
+        Self {
+            starting_lives: STARTING_LIVES,
+            respawn_delay: RESPAWN_DELAY,
+            respawn_clear_radius: RESPAWN_CLEAR_RADIUS,
+            game_over_delay: GAME_OVER_DELAY,
+            respawn_invulnerability: RESPAWN_INVULNERABILITY,
+            blink_interval: BLINK_INTERVAL,
+            extra_life_every: EXTRA_LIFE_EVERY,
+        }
+    }
+}
+
+/// The difficulty curve from one wave to the next.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct WaveConfig {
+    /// The wave a new game starts on.
+    pub starting_wave: u32,
+    pub extra_asteroids_per_wave: usize,
+    /// Cap on big asteroids per wave, however far the player gets.
+    pub max_asteroids: usize,
+    /// Fraction of `physics.asteroid_velocity` added per wave.
+    pub speed_increase_per_wave: f32,
+    /// Seconds the "Wave N" banner shows before the asteroids arrive.
+    pub interstitial: f32,
+}
+
+impl Default for WaveConfig {
+    fn default() -> Self {
+        Self {
+            starting_wave: 1,
+            extra_asteroids_per_wave: EXTRA_ASTEROIDS_PER_WAVE,
+            max_asteroids: MAX_ASTEROIDS_PER_WAVE,
+            speed_increase_per_wave: SPEED_INCREASE_PER_WAVE,
+            interstitial: WAVE_INTERSTITIAL,
+        }
+    }
+}
+
+/// Flying saucers. Speeds are in world units per second, times in seconds.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct SaucerConfig {
+    /// Time between one saucer leaving and the next one appearing.
+    pub spawn_interval: f32,
+    pub large_speed: f32,
+    pub small_speed: f32,
+    /// How often a saucer may change its vertical heading.
+    pub turn_interval: f32,
+    pub fire_interval: f32,
+    pub bullet_velocity: f32,
+    /// Score at which most saucers are small ones.
+    pub small_saucer_score: u32,
+    /// Score at which small saucers stop missing.
+    pub perfect_aim_score: u32,
+}
+
+impl Default for SaucerConfig {
+    fn default() -> Self {
+        Self {
+            spawn_interval: SAUCER_SPAWN_INTERVAL,
+            large_speed: LARGE_SAUCER_SPEED,
+            small_speed: SMALL_SAUCER_SPEED,
+            turn_interval: SAUCER_TURN_INTERVAL,
+            fire_interval: SAUCER_FIRE_INTERVAL,
+            bullet_velocity: SAUCER_BULLET_VELOCITY,
+            small_saucer_score: SMALL_SAUCER_SCORE,
+            perfect_aim_score: PERFECT_AIM_SCORE,
+        }
+    }
+}
+
+/// How asteroids break apart. Fragments keep their parent's velocity and
+/// fan out sideways to the hit; spreads are multiples of the current
+/// wave's asteroid speed.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct SplitConfig {
+    /// Medium asteroids a big one breaks into.
+    pub big_fragments: usize,
+    /// Small asteroids a medium one breaks into.
+    pub medium_fragments: usize,
+    pub medium_spread: f32,
+    pub small_spread: f32,
+}
+
+impl SplitConfig {
+    /// How many pieces an asteroid of `size` breaks into.
+    pub fn fragments(&self, size: AsteroidSize) -> usize {
+        match size {
+            AsteroidSize::Big => self.big_fragments,
+            AsteroidSize::Medium => self.medium_fragments,
+            AsteroidSize::Small => 0,
+        }
+    }
+
+    /// Sideways speed, relative to the wave's asteroid speed, given to
+    /// fragments of `size`.
+    pub fn spread(&self, size: AsteroidSize) -> f32 {
+        match size {
+            AsteroidSize::Big => 0.0,
+            AsteroidSize::Medium => self.medium_spread,
+            AsteroidSize::Small => self.small_spread,
+        }
+    }
+}
+
+impl Default for SplitConfig {
+    fn default() -> Self {
+        Self {
+            big_fragments: FRAGMENTS_PER_SPLIT,
+            medium_fragments: FRAGMENTS_PER_SPLIT,
+            medium_spread: MEDIUM_FRAGMENT_SPREAD,
+            small_spread: SMALL_FRAGMENT_SPREAD,
+        }
+    }
+}
+
+/// The starship's hyperspace jump.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct HyperspaceConfig {
+    /// Seconds the starship is gone before reappearing.
+    pub duration: f32,
+    /// Chance, from 0 to 1, that the starship explodes on re-entry.
+    pub failure_chance: f32,
+}
+
+impl Default for HyperspaceConfig {
+    fn default() -> Self {
+        Self {
+            duration: HYPERSPACE_DURATION,
+            failure_chance: HYPERSPACE_FAILURE_CHANCE,
+        }
+    }
+}
+
+/// The starship's gun. Times are in seconds.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct BulletConfig {
+    /// The starship cannot fire while this many of its bullets are in flight.
+    pub max_on_screen: usize,
+    /// Minimum time between two shots, however fast fire is tapped.
+    pub cooldown: f32,
+    /// Keep firing every `auto_fire_interval` while fire is held.
+    pub auto_fire: bool,
+    pub auto_fire_interval: f32,
+    /// Add the starship's velocity to its bullets so it cannot overtake
+    /// them; off, every bullet flies at `physics.bullet_velocity` as in
+    /// the arcade.
+    pub inherit_ship_velocity: bool,
+    /// How long any bullet, the starship's or a saucer's, stays in flight.
+    pub lifetime: BulletLifetime,
+}
+
+impl Default for BulletConfig {
+    fn default() -> Self {
+        Self {
+            max_on_screen: MAX_BULLETS,
+            cooldown: FIRE_COOLDOWN,
+            auto_fire: false,
+            auto_fire_interval: AUTO_FIRE_INTERVAL,
+            inherit_ship_velocity: true,
+            lifetime: BulletLifetime::Distance(BULLET_DISTANCE),
+        }
+    }
+}
+
+/// When a bullet expires. Both count what the bullet actually flew, so a
+/// bullet that wraps across an edge keeps its full range.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum BulletLifetime {
+    /// World units travelled.
+    Distance(f32),
+    Seconds(f32),
+}
+
+impl BulletLifetime {
+    pub fn expired(&self, distance: f32, seconds: f32) -> bool {
+        match *self {
+            BulletLifetime::Distance(limit) => distance > limit,
+            BulletLifetime::Seconds(limit) => seconds > limit,
+        }
+    }
+}
+
+/// Explosions, ship debris and exhaust. Speeds are in world units per
+/// second, times in seconds.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct ParticleConfig {
+    /// Size of the particle pool; effects past it are dropped. `0` turns
+    /// particles off.
+    pub max_particles: usize,
+    /// Sparks thrown out by a big asteroid; smaller ones throw fewer.
+    pub explosion_particles: usize,
+    /// Fastest spark from a big asteroid; smaller ones burst slower.
+    pub explosion_speed: f32,
+    pub spark_lifetime: f32,
+    pub debris_speed: f32,
+    pub debris_lifetime: f32,
+    /// Exhaust particles per second while thrusting.
+    pub exhaust_rate: f32,
+    pub exhaust_speed: f32,
+    pub exhaust_lifetime: f32,
+}
+
+impl ParticleConfig {
+    /// Spark count and top speed for an asteroid of `size` blowing up.
+    pub fn explosion(&self, size: AsteroidSize) -> (usize, f32) {
+        let scale = size.scale() / AsteroidSize::Big.scale();
+        let count = (self.explosion_particles as f32 * scale).round() as usize;
+
+        (count, self.explosion_speed * scale.sqrt())
+    }
+}
+
+impl Default for ParticleConfig {
+    fn default() -> Self {
+        Self {
+            max_particles: MAX_PARTICLES,
+            explosion_particles: EXPLOSION_PARTICLES,
+            explosion_speed: EXPLOSION_SPEED,
+            spark_lifetime: SPARK_LIFETIME,
+            debris_speed: DEBRIS_SPEED,
+            debris_lifetime: DEBRIS_LIFETIME,
+            exhaust_rate: EXHAUST_RATE,
+            exhaust_speed: EXHAUST_SPEED,
+            exhaust_lifetime: EXHAUST_LIFETIME,
+        }
+    }
+}
+
+/// Starting volumes and the heartbeat's tempo. Volumes are linear, from 0
+/// to 1; the sound effects and music buses are both scaled by `master`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct AudioConfig {
+    pub master_volume: f32,
+    pub sfx_volume: f32,
+    pub music_volume: f32,
+    pub muted: bool,
+    /// Seconds between heartbeats at the start of a wave.
+    pub slowest_beat: f32,
+    /// Seconds between heartbeats with the last asteroid left.
+    pub fastest_beat: f32,
+}
+
+impl AudioConfig {
+    /// Seconds between heartbeats with `remaining` of the wave, from 1 at
+    /// its start down to 0, still to be shot.
+    pub fn beat_interval(&self, remaining: f32) -> f32 {
+        self.fastest_beat + (self.slowest_beat - self.fastest_beat) * remaining.clamp(0.0, 1.0)
+    }
+}
+
+impl Default for AudioConfig {
+    fn default() -> Self {
+        Self {
+            master_volume: MASTER_VOLUME,
+            sfx_volume: SFX_VOLUME,
+            music_volume: MUSIC_VOLUME,
+            muted: false,
+            slowest_beat: SLOWEST_BEAT,
+            fastest_beat: FASTEST_BEAT,
+        }
+    }
+}
+
+/// Gamepad handling beyond the button bindings. Times are in seconds.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct GamepadConfig {
+    /// How far, from 0 to 1, the left stick must be pushed before the
+    /// starship starts to turn.
+    pub stick_dead_zone: f32,
+    /// Shake gamepads that support it when the starship is destroyed, and
+    /// lightly when it destroys an asteroid.
+    pub rumble: bool,
+    pub hit_rumble_seconds: f32,
+    pub shot_rumble_seconds: f32,
+    /// Pause a game in progress when a gamepad is unplugged.
+    pub pause_on_disconnect: bool,
+}
+
+impl Default for GamepadConfig {
+    fn default() -> Self {
+        Self {
+            stick_dead_zone: STICK_DEAD_ZONE,
+            rumble: true,
+            hit_rumble_seconds: HIT_RUMBLE_SECONDS,
+            shot_rumble_seconds: SHOT_RUMBLE_SECONDS,
+            pause_on_disconnect: true,
+        }
+    }
+}
+
+/// The on-screen touch controls. Distances are in logical pixels.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct TouchConfig {
+    /// How far the virtual stick's knob can be dragged from where the
+    /// touch started.
+    pub stick_radius: f32,
+    /// How far, from 0 to 1 of `stick_radius`, the stick must be pushed
+    /// before the starship turns to follow it.
+    pub dead_zone: f32,
+    /// Pushed further than this, from 0 to 1, the starship also thrusts,
+    /// harder the closer the knob is to the edge; short of it, it only
+    /// turns.
+    pub thrust_threshold: f32,
+    /// How far a touch on the right half must flick upwards to jump to
+    /// hyperspace.
+    pub swipe_distance: f32,
+    /// How long, in seconds, a touch on the right half must be held
+    /// before it auto-fires. A quicker touch is a tap, which fires, or a
+    /// flick, told apart when it lifts.
+    pub hold_delay: f32,
+    /// Tapping the right half turns the starship towards the tap and
+    /// fires once it faces it, instead of firing straight ahead.
+    pub aim_at_tap: bool,
+    /// How close, in radians, the starship must face a tap before it
+    /// fires at it.
+    pub aim_tolerance: f32,
+}
+
+impl Default for TouchConfig {
+    fn default() -> Self {
+        Self {
+            stick_radius: TOUCH_STICK_RADIUS,
+            dead_zone: TOUCH_DEAD_ZONE,
+            thrust_threshold: TOUCH_THRUST_THRESHOLD,
+            swipe_distance: SWIPE_DISTANCE,
+            hold_delay: TOUCH_HOLD_DELAY,
+            aim_at_tap: false,
+            aim_tolerance: AIM_TOLERANCE,
+        }
+    }
+}
+
+/// Optional parts of the game that an embedding app may want to turn off.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct FeatureFlags {
+    /// Spawn a `Camera2d` at startup (only used by the render plugin).
+    pub spawn_camera: bool,
+    /// Drive the starship from the keys in `bindings`.
+    pub keyboard_input: bool,
+    /// Drive the starship from any connected gamepad.
+    pub gamepad_input: bool,
+    /// Read the on-screen buttons exported to JS (see `mobile_left_down` & co).
+    pub mobile_input: bool,
+    /// Drive the starship from touches on the window: a virtual stick on
+    /// the left half, fire on the right.
+    pub touch_input: bool,
+    /// Follow primary window resizes instead of keeping the configured size.
+    pub track_window_size: bool,
+    /// Start on the title screen; otherwise the first game starts at once.
+    pub title_screen: bool,
+    /// Spawn the next wave when the field is cleared.
+    pub waves: bool,
+    /// Send flying saucers across the screen during play.
+    pub saucers: bool,
+}
+
+impl Default for FeatureFlags {
+    fn default() -> Self {
+        Self {
+            spawn_camera: true,
+            keyboard_input: true,
+            gamepad_input: true,
+            mobile_input: cfg!(target_arch = "wasm32"),
+            touch_input: true,
+            track_window_size: true,
+            title_screen: true,
+            waves: true,
+            saucers: true,
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use bevy::input::gamepad::{
+    GamepadConnection, GamepadConnectionEvent, GamepadRumbleIntensity, GamepadRumbleRequest,
+};
+use bevy::prelude::*;
+
+use crate::config::AsteroidsConfig;
+use crate::hits::AsteroidDestroyed;
+use crate::input::{Action, ActionState};
+use crate::state::ShipDestroyed;
+
+/// Gamepads come and go at any time; [`gather_actions`](crate::input)
+/// reads whichever are connected each frame. This only reports them, and
+/// presses [`Action::Pause`] when one is unplugged so the player does not
+/// lose a ship while reconnecting. Going through the pause action, the
+/// pause is recorded with the rest of the input and only a game in
+/// progress is paused.
+pub(crate) fn handle_gamepad_connections(
+    mut connections: MessageReader<GamepadConnectionEvent>,
+    mut actions: ResMut<ActionState>,
+    time: Res<Time<Virtual>>,
+    config: Res<AsteroidsConfig>,
+) {
+    for event in connections.read() {
+        match &event.connection {
+            GamepadConnection::Connected { name, .. } => {
+                info!("gamepad connected: {name}");
+            }
+            GamepadConnection::Disconnected => {
+                info!("gamepad disconnected");
+
+                // pressing pause again would unpause
+                if config.gamepad.pause_on_disconnect && !time.is_paused() {
+                    actions.tap(Action::Pause);
+                }
+            }
+        }
+    }
+}
+
+/// Shakes every connected gamepad: hard when the starship is destroyed,
+/// briefly when an asteroid is.
+pub(crate) fn rumble_on_hits(
+    mut ships: MessageReader<ShipDestroyed>,
+    mut asteroids: MessageReader<AsteroidDestroyed>,
+    mut rumble: MessageWriter<GamepadRumbleRequest>,
+    gamepads: Query<Entity, With<Gamepad>>,
+    config: Res<AsteroidsConfig>,
+) {
+    let hit = ships.read().count() > 0;
+    let shot = asteroids.read().count() > 0;
+
+    let (intensity, seconds) = if hit {
+        (
+            GamepadRumbleIntensity::MAX,
+            config.gamepad.hit_rumble_seconds,
+        )
+    } else if shot {
+        (
+            GamepadRumbleIntensity::weak_motor(0.4),
+            config.gamepad.shot_rumble_seconds,
+        )
+    } else {
+        return;
+    };
+
+    for gamepad in &gamepads {
+        rumble.write(GamepadRumbleRequest::Add {
+            duration: Duration::from_secs_f32(seconds),
+            intensity,
+            gamepad,
+        });
+    }
+}
@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+
+use crate::collision::Collider;
+use crate::components::*;
+use crate::config::AsteroidsConfig;
+use crate::systems::ScreenBounds;
+
+/// Every asteroid's [`Collider`], bucketed into a uniform grid over the
+/// wrapping playfield so a collision check only looks at nearby rocks.
+/// Rebuilt once per fixed step, after movement.
+#[derive(Resource, Default)]
+pub struct SpatialGrid {
+    columns: usize,
+    rows: usize,
+    cell: Vec2,
+    half_extents: Vec2,
+    /// Indices into `entries` for each cell, row by row.
+    cells: Vec<Vec<usize>>,
+    entries: Vec<(Entity, Collider)>,
+}
+
+impl SpatialGrid {
+    /// Empties the grid and fits it to `bounds`, with cells of roughly
+    /// `cell_size`, stretched so a whole number of them spans the screen.
+    pub fn reset(&mut self, bounds: &ScreenBounds, cell_size: f32) {
+        self.half_extents = Vec2::new(bounds.half_width, bounds.half_height);
+        let size = 2.0 * self.half_extents;
+        self.columns = (size.x / cell_size).floor().max(1.0) as usize;
+        self.rows = (size.y / cell_size).floor().max(1.0) as usize;
+        self.cell = size / Vec2::new(self.columns as f32, self.rows as f32);
+
+        self.cells.resize_with(self.columns * self.rows, Vec::new);
+        self.cells.truncate(self.columns * self.rows);
+        for cell in &mut self.cells {
+            cell.clear();
+        }
+        self.entries.clear();
+    }
+
+    pub fn insert(&mut self, entity: Entity, collider: Collider) {
+        let index = self.entries.len();
+        let (centre, radius) = collider.bounding_circle();
+
+        for cell in self.cells_around(centre, radius) {
+            self.cells[cell].push(index);
+        }
+        self.entries.push((entity, collider));
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entities whose colliders overlap `collider`, edges wrapping.
+    pub fn overlapping(&self, collider: &Collider, bounds: &ScreenBounds) -> Vec<Entity> {
+        let (centre, radius) = collider.bounding_circle();
+        let mut candidates: Vec<usize> = self
+            .cells_around(centre, radius)
+            .flat_map(|cell| self.cells[cell].iter().copied())
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        candidates
+            .into_iter()
+            .map(|index| &self.entries[index])
+            .filter(|(_, other)| collider.overlaps_wrapped(other, bounds))
+            .map(|(entity, _)| *entity)
+            .collect()
+    }
+
+    /// Cells touched by a circle, wrapping at the edges.
+    fn cells_around(&self, centre: Vec2, radius: f32) -> impl Iterator<Item = usize> {
+        let columns = self.columns;
+        let rows = self.rows;
+        let min = ((centre - radius + self.half_extents) / self.cell).floor();
+        let max = ((centre + radius + self.half_extents) / self.cell).floor();
+        // A circle wider than the grid touches every cell once.
+        let span_x = ((max.x - min.x) as usize + 1).min(columns);
+        let span_y = ((max.y - min.y) as usize + 1).min(rows);
+
+        (0..span_y).flat_map(move |dy| {
+            let row = (min.y as i64 + dy as i64).rem_euclid(rows as i64) as usize;
+            (0..span_x).map(move |dx| {
+                let column = (min.x as i64 + dx as i64).rem_euclid(columns as i64) as usize;
+                row * columns + column
+            })
+        })
+    }
+}
+
+pub(crate) fn rebuild_spatial_grid(
+    mut grid: ResMut<SpatialGrid>,
+    bounds: Res<ScreenBounds>,
+    config: Res<AsteroidsConfig>,
+    asteroids: Query<(Entity, &AsteroidShape, &Spin, &Position, &Diameter), With<Asteroid>>,
+) {
+    grid.reset(&bounds, config.physics.collision_cell_size);
+
+    for (entity, shape, spin, position, diameter) in &asteroids {
+        grid.insert(entity, Collider::asteroid(shape, spin, position, diameter));
+    }
+}
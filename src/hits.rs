@@ -0,0 +1,123 @@
+use bevy::ecs::entity::EntityHashSet;
+use bevy::prelude::*;
+
+use crate::components::*;
+use crate::config::AsteroidsConfig;
+use crate::rng::GameRng;
+use crate::saucer::{Hostile, Saucer};
+use crate::score::Scored;
+use crate::state::ShipDestroyed;
+use crate::systems::{split_asteroid, Impact, ScreenBounds};
+use crate::wave::Wave;
+
+/// Sent by the collision systems when `attacker` runs into `target`.
+/// Nothing is destroyed until [`AsteroidsSet::Resolve`](crate::AsteroidsSet)
+/// applies the hits, in the order they were sent.
+#[derive(Message, Clone, Copy, Debug)]
+pub struct Hit {
+    pub attacker: Entity,
+    pub target: Entity,
+    /// Which way the attacker was moving into the target; asteroids split
+    /// across it.
+    pub direction: Vec2,
+}
+
+/// Sent for every asteroid destroyed, whoever hit it, for effects.
+#[derive(Message, Clone, Copy, Debug)]
+pub struct AsteroidDestroyed {
+    pub size: AsteroidSize,
+    pub position: Vec2,
+    pub velocity: Vec2,
+}
+
+/// Applies this step's hits. Each entity is destroyed at most once: a hit
+/// involving something an earlier hit already used up is dropped, so two
+/// bullets cannot split the same asteroid and one bullet cannot break two.
+///
+/// Both sides of a hit are destroyed, except an asteroid that runs into
+/// something, which carries on. The player scores for targets hit by the
+/// starship or its bullets.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn resolve_hits(
+    mut hits: MessageReader<Hit>,
+    mut commands: Commands,
+    mut rng: ResMut<GameRng>,
+    mut scored_writer: MessageWriter<Scored>,
+    mut destroyed_writer: MessageWriter<ShipDestroyed>,
+    mut asteroid_writer: MessageWriter<AsteroidDestroyed>,
+    bounds: Res<ScreenBounds>,
+    config: Res<AsteroidsConfig>,
+    wave: Res<Wave>,
+    bullets: Query<Has<Hostile>, With<Bullet>>,
+    asteroids: Query<(&Asteroid, &Position, &Velocity)>,
+    saucers: Query<(&Saucer, &Position)>,
+    starship: Query<(&Starship, &Position, &Velocity)>,
+) {
+    let wave_speed = config.wave_asteroid_velocity(wave.number);
+    let mut consumed = EntityHashSet::default();
+
+    for hit in hits.read() {
+        if consumed.contains(&hit.attacker) || consumed.contains(&hit.target) {
+            continue;
+        }
+
+        let by_player = starship.contains(hit.attacker)
+            || bullets.get(hit.attacker).is_ok_and(|hostile| !hostile);
+        let attacker_destroyed = !asteroids.contains(hit.attacker);
+
+        consumed.insert(hit.target);
+        if attacker_destroyed {
+            consumed.insert(hit.attacker);
+        }
+
+        let destroyed = [Some(hit.target), attacker_destroyed.then_some(hit.attacker)];
+        for entity in destroyed.into_iter().flatten() {
+            let scores = by_player && entity == hit.target;
+
+            if let Ok((asteroid, position, velocity)) = asteroids.get(entity) {
+                split_asteroid(
+                    &mut commands,
+                    &mut rng,
+                    &bounds,
+                    &config,
+                    wave_speed,
+                    Impact {
+                        entity,
+                        size: asteroid.size,
+                        position: position.0,
+                        velocity: velocity.0,
+                        direction: hit.direction,
+                    },
+                );
+                asteroid_writer.write(AsteroidDestroyed {
+                    size: asteroid.size,
+                    position: position.0,
+                    velocity: velocity.0,
+                });
+                if scores {
+                    scored_writer.write(Scored {
+                        points: asteroid.size.points(),
+                        position: position.0,
+                    });
+                }
+            } else if let Ok((saucer, position)) = saucers.get(entity) {
+                commands.entity(entity).despawn();
+                if scores {
+                    scored_writer.write(Scored {
+                        points: saucer.size.points(),
+                        position: position.0,
+                    });
+                }
+            } else if let Ok((ship, position, velocity)) = starship.get(entity) {
+                commands.entity(entity).despawn();
+                destroyed_writer.write(ShipDestroyed {
+                    position: position.0,
+                    velocity: velocity.0,
+                    rotation: ship.rotation_angle,
+                });
+            } else {
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+}
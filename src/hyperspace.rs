@@ -0,0 +1,77 @@
+use bevy::prelude::*;
+
+use crate::components::*;
+use crate::config::AsteroidsConfig;
+use crate::input::{Action, ActionState};
+use crate::rng::GameRng;
+use crate::state::ShipDestroyed;
+use crate::systems::ScreenBounds;
+
+/// Put on the starship while it is in hyperspace: it is invisible, cannot
+/// be hit and does not respond to controls until the timer runs out.
+#[derive(Component)]
+pub struct InHyperspace(Timer);
+
+impl InHyperspace {
+    /// How far through the jump the starship is, from 0 to 1.
+    pub fn progress(&self) -> f32 {
+        self.0.fraction()
+    }
+}
+
+/// Matches the starship while it is in normal space.
+pub(crate) type ActiveStarship = (With<Starship>, Without<InHyperspace>);
+
+/// Sends the starship to a random point on the frame hyperspace is
+/// pressed. Like firing, this runs in `Update` so a press is never missed.
+pub(crate) fn enter_hyperspace(
+    mut commands: Commands,
+    actions: Res<ActionState>,
+    mut rng: ResMut<GameRng>,
+    bounds: Res<ScreenBounds>,
+    config: Res<AsteroidsConfig>,
+    mut query: Query<(Entity, &mut Position, &mut Velocity), ActiveStarship>,
+) {
+    if !actions.just_pressed(Action::Hyperspace) {
+        return;
+    }
+
+    for (entity, mut position, mut velocity) in &mut query {
+        position.0 = rng.random_point(&bounds);
+        velocity.0 = Vec2::ZERO;
+        commands
+            .entity(entity)
+            .insert(InHyperspace(Timer::from_seconds(
+                config.hyperspace.duration,
+                TimerMode::Once,
+            )));
+    }
+}
+
+/// Brings the starship back once its time in hyperspace is up, or blows it
+/// up with a chance of `hyperspace.failure_chance`.
+pub(crate) fn leave_hyperspace(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut rng: ResMut<GameRng>,
+    mut destroyed_writer: MessageWriter<ShipDestroyed>,
+    config: Res<AsteroidsConfig>,
+    mut query: Query<(Entity, &mut InHyperspace, &Starship, &Position)>,
+) {
+    for (entity, mut hyperspace, starship, position) in &mut query {
+        if !hyperspace.0.tick(time.delta()).is_finished() {
+            continue;
+        }
+
+        if rng.unit() < config.hyperspace.failure_chance {
+            commands.entity(entity).despawn();
+            destroyed_writer.write(ShipDestroyed {
+                position: position.0,
+                velocity: Vec2::ZERO,
+                rotation: starship.rotation_angle,
+            });
+        } else {
+            commands.entity(entity).remove::<InHyperspace>();
+        }
+    }
+}
@@ -0,0 +1,620 @@
+use std::fmt;
+
+use bevy::prelude::*;
+
+use crate::config::AsteroidsConfig;
+use crate::mobile::MobileInputState;
+use crate::state::GameState;
+use crate::touch::TouchControls;
+
+/// Something the player can do, whatever device it comes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Action {
+    RotateLeft,
+    RotateRight,
+    Thrust,
+    Fire,
+    Hyperspace,
+    Pause,
+}
+
+impl Action {
+    pub const ALL: [Action; 6] = [
+        Action::RotateLeft,
+        Action::RotateRight,
+        Action::Thrust,
+        Action::Fire,
+        Action::Hyperspace,
+        Action::Pause,
+    ];
+
+    /// The action's name in a bindings file.
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::RotateLeft => "rotate_left",
+            Action::RotateRight => "rotate_right",
+            Action::Thrust => "thrust",
+            Action::Fire => "fire",
+            Action::Hyperspace => "hyperspace",
+            Action::Pause => "pause",
+        }
+    }
+
+    /// The action's name in the rebinding menu.
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::RotateLeft => "ROTATE LEFT",
+            Action::RotateRight => "ROTATE RIGHT",
+            Action::Thrust => "THRUST",
+            Action::Fire => "FIRE",
+            Action::Hyperspace => "HYPERSPACE",
+            Action::Pause => "PAUSE",
+        }
+    }
+
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// This frame's input, merged from every device. Game systems read this
+/// instead of the keyboard, gamepads, touches or the on-screen buttons, so
+/// they work the same whatever drives them. A script can drive it too, with
+/// [`press`](Self::press) and [`release`](Self::release) from a `PreUpdate`
+/// system after [`AsteroidsSet::Input`](crate::AsteroidsSet).
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq)]
+pub struct ActionState {
+    pub(crate) pressed: u8,
+    pub(crate) just_pressed: u8,
+    /// What was pressed the frame before, so an action a script holds
+    /// down every frame is only just pressed on the first.
+    pub(crate) held_before: u8,
+    pub(crate) rotation: f32,
+    pub(crate) thrust: f32,
+}
+
+impl ActionState {
+    /// How hard to turn, from 1 (full left) to -1 (full right). Keys turn at
+    /// full rate, a stick in proportion to how far it is pushed.
+    pub fn rotation(&self) -> f32 {
+        self.rotation
+    }
+
+    /// How hard to thrust, from 0 to 1; a trigger can thrust part way.
+    pub fn thrust(&self) -> f32 {
+        self.thrust
+    }
+
+    pub fn pressed(&self, action: Action) -> bool {
+        self.pressed & action.bit() != 0
+    }
+
+    /// Pressed this frame. A tap that was released within the frame still
+    /// counts, though [`pressed`](Self::pressed) never saw it.
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.just_pressed & action.bit() != 0
+    }
+
+    /// Holds `action` down, for scripted input.
+    pub fn press(&mut self, action: Action) {
+        if (self.pressed | self.held_before) & action.bit() == 0 {
+            self.just_pressed |= action.bit();
+        }
+        self.pressed |= action.bit();
+        self.digital_axes();
+    }
+
+    pub fn release(&mut self, action: Action) {
+        self.pressed &= !action.bit();
+        self.digital_axes();
+    }
+
+    /// Full turn and thrust for the held actions, as from keys.
+    fn digital_axes(&mut self) {
+        self.rotation = if self.pressed(Action::RotateLeft) {
+            1.0
+        } else if self.pressed(Action::RotateRight) {
+            -1.0
+        } else {
+            0.0
+        };
+        self.thrust = if self.pressed(Action::Thrust) {
+            1.0
+        } else {
+            0.0
+        };
+    }
+
+    /// Presses `action` for this frame only.
+    pub fn tap(&mut self, action: Action) {
+        self.just_pressed |= action.bit();
+    }
+}
+
+/// Which keys and gamepad buttons trigger which action. Starts from
+/// [`AsteroidsConfig::bindings`](crate::AsteroidsConfig) and can be changed
+/// from the pause menu. A key or button drives at most one action.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct InputBindings {
+    keys: Vec<(Action, KeyCode)>,
+    buttons: Vec<(Action, GamepadButton)>,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        Self {
+            keys: vec![
+                (Action::RotateLeft, KeyCode::ArrowLeft),
+                (Action::RotateRight, KeyCode::ArrowRight),
+                (Action::Thrust, KeyCode::ArrowUp),
+                (Action::Fire, KeyCode::Space),
+                (Action::Hyperspace, KeyCode::ShiftLeft),
+                (Action::Hyperspace, KeyCode::ShiftRight),
+                (Action::Pause, KeyCode::Escape),
+                (Action::Pause, KeyCode::KeyP),
+            ],
+            buttons: vec![
+                (Action::RotateLeft, GamepadButton::DPadLeft),
+                (Action::RotateRight, GamepadButton::DPadRight),
+                (Action::Thrust, GamepadButton::RightTrigger2),
+                (Action::Fire, GamepadButton::South),
+                (Action::Hyperspace, GamepadButton::East),
+                (Action::Pause, GamepadButton::Start),
+            ],
+        }
+    }
+}
+
+/// Adds `input` to `action` in `list`, taking it away from any other action.
+fn add_binding<T: Copy + PartialEq>(list: &mut Vec<(Action, T)>, action: Action, input: T) {
+    list.retain(|(_, bound)| *bound != input);
+    // kept in action order, so equal bindings compare equal
+    let at = list
+        .iter()
+        .position(|(bound, _)| *bound > action)
+        .unwrap_or(list.len());
+    list.insert(at, (action, input));
+}
+
+impl InputBindings {
+    pub fn keys(&self, action: Action) -> impl Iterator<Item = KeyCode> + '_ {
+        self.keys
+            .iter()
+            .filter(move |(bound, _)| *bound == action)
+            .map(|(_, key)| *key)
+    }
+
+    pub fn buttons(&self, action: Action) -> impl Iterator<Item = GamepadButton> + '_ {
+        self.buttons
+            .iter()
+            .filter(move |(bound, _)| *bound == action)
+            .map(|(_, button)| *button)
+    }
+
+    /// Adds `key` to `action`, taking it away from any other action.
+    pub fn add(&mut self, action: Action, key: KeyCode) {
+        add_binding(&mut self.keys, action, key);
+    }
+
+    /// Makes `key` the only key for `action`; its buttons are kept.
+    pub fn bind(&mut self, action: Action, key: KeyCode) {
+        self.keys.retain(|(bound, _)| *bound != action);
+        self.add(action, key);
+    }
+
+    /// Adds `button` to `action`, taking it away from any other action.
+    pub fn add_button(&mut self, action: Action, button: GamepadButton) {
+        add_binding(&mut self.buttons, action, button);
+    }
+
+    /// Makes `button` the only gamepad button for `action`; its keys are
+    /// kept.
+    pub fn bind_button(&mut self, action: Action, button: GamepadButton) {
+        self.buttons.retain(|(bound, _)| *bound != action);
+        self.add_button(action, button);
+    }
+
+    /// The keys and buttons for `action`, as named in a bindings file.
+    pub fn names(&self, action: Action) -> Vec<String> {
+        let keys = self.keys(action).map(|key| format!("{key:?}"));
+        let buttons = self.buttons(action).map(|button| format!("{button:?}"));
+        keys.chain(buttons).collect()
+    }
+
+    /// Reads a bindings file: one `action = Key, Button` line per action,
+    /// with keys named as in `KeyCode` (`ArrowUp`, `KeyW`, `Space`...) and
+    /// buttons as in `GamepadButton` (`South`, `RightTrigger2`...). Actions
+    /// not listed keep their defaults; an empty list unbinds one. Lines
+    /// starting with `#` are comments.
+    pub fn parse(text: &str) -> Result<Self, BindingsError> {
+        let mut bindings = Self::default();
+
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((name, keys)) = line.split_once('=') else {
+                return Err(BindingsError::MissingEquals(line_number));
+            };
+            let name = name.trim();
+            let action = Action::ALL
+                .into_iter()
+                .find(|action| action.name() == name)
+                .ok_or_else(|| BindingsError::UnknownAction(line_number, name.into()))?;
+
+            bindings.keys.retain(|(bound, _)| *bound != action);
+            bindings.buttons.retain(|(bound, _)| *bound != action);
+            for name in keys
+                .split(',')
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+            {
+                if let Some(key) = parse_key(name) {
+                    bindings.add(action, key);
+                } else if let Some(button) = parse_button(name) {
+                    bindings.add_button(action, button);
+                } else {
+                    return Err(BindingsError::UnknownKey(line_number, name.into()));
+                }
+            }
+        }
+
+        Ok(bindings)
+    }
+}
+
+/// Writes the bindings in the format [`InputBindings::parse`] reads.
+impl fmt::Display for InputBindings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for action in Action::ALL {
+            writeln!(f, "{} = {}", action.name(), self.names(action).join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Why a bindings file could not be read, with the 1-based line number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BindingsError {
+    MissingEquals(usize),
+    UnknownAction(usize, String),
+    /// Neither a key nor a gamepad button.
+    UnknownKey(usize, String),
+}
+
+impl fmt::Display for BindingsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BindingsError::MissingEquals(line) => {
+                write!(f, "line {line}: expected `action = keys`")
+            }
+            BindingsError::UnknownAction(line, name) => {
+                write!(f, "line {line}: unknown action `{name}`")
+            }
+            BindingsError::UnknownKey(line, name) => write!(f, "line {line}: unknown key `{name}`"),
+        }
+    }
+}
+
+impl std::error::Error for BindingsError {}
+
+/// Keys that can be named in a bindings file or picked in the menu.
+const BINDABLE_KEYS: [KeyCode; 64] = [
+    KeyCode::KeyA,
+    KeyCode::KeyB,
+    KeyCode::KeyC,
+    KeyCode::KeyD,
+    KeyCode::KeyE,
+    KeyCode::KeyF,
+    KeyCode::KeyG,
+    KeyCode::KeyH,
+    KeyCode::KeyI,
+    KeyCode::KeyJ,
+    KeyCode::KeyK,
+    KeyCode::KeyL,
+    KeyCode::KeyM,
+    KeyCode::KeyN,
+    KeyCode::KeyO,
+    KeyCode::KeyP,
+    KeyCode::KeyQ,
+    KeyCode::KeyR,
+    KeyCode::KeyS,
+    KeyCode::KeyT,
+    KeyCode::KeyU,
+    KeyCode::KeyV,
+    KeyCode::KeyW,
+    KeyCode::KeyX,
+    KeyCode::KeyY,
+    KeyCode::KeyZ,
+    KeyCode::Digit0,
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+    KeyCode::Numpad0,
+    KeyCode::Numpad1,
+    KeyCode::Numpad2,
+    KeyCode::Numpad3,
+    KeyCode::Numpad4,
+    KeyCode::Numpad5,
+    KeyCode::Numpad6,
+    KeyCode::Numpad7,
+    KeyCode::Numpad8,
+    KeyCode::Numpad9,
+    KeyCode::ArrowUp,
+    KeyCode::ArrowDown,
+    KeyCode::ArrowLeft,
+    KeyCode::ArrowRight,
+    KeyCode::Space,
+    KeyCode::Enter,
+    KeyCode::Tab,
+    KeyCode::Backspace,
+    KeyCode::Escape,
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::AltLeft,
+    KeyCode::AltRight,
+    KeyCode::Comma,
+    KeyCode::Period,
+    KeyCode::Slash,
+];
+
+/// Gamepad buttons that can be named in a bindings file or picked in the
+/// menu, which `Select` leaves.
+const BINDABLE_BUTTONS: [GamepadButton; 16] = [
+    GamepadButton::South,
+    GamepadButton::East,
+    GamepadButton::North,
+    GamepadButton::West,
+    GamepadButton::LeftTrigger,
+    GamepadButton::LeftTrigger2,
+    GamepadButton::RightTrigger,
+    GamepadButton::RightTrigger2,
+    GamepadButton::Start,
+    GamepadButton::Mode,
+    GamepadButton::LeftThumb,
+    GamepadButton::RightThumb,
+    GamepadButton::DPadUp,
+    GamepadButton::DPadDown,
+    GamepadButton::DPadLeft,
+    GamepadButton::DPadRight,
+];
+
+fn parse_key(name: &str) -> Option<KeyCode> {
+    BINDABLE_KEYS
+        .into_iter()
+        .find(|key| format!("{key:?}") == name)
+}
+
+fn parse_button(name: &str) -> Option<GamepadButton> {
+    BINDABLE_BUTTONS
+        .into_iter()
+        .find(|button| format!("{button:?}") == name)
+}
+
+/// How far a stick is pushed past `dead_zone`, rescaled to run from 0 to 1
+/// again and keeping its sign.
+pub(crate) fn past_dead_zone(value: f32, dead_zone: f32) -> f32 {
+    let pushed = (value.abs() - dead_zone) / (1.0 - dead_zone);
+    pushed.clamp(0.0, 1.0).copysign(value)
+}
+
+/// The pause menu's cursor, and whether it is waiting for a key to bind to
+/// the selected action.
+#[derive(Resource, Default, Debug)]
+pub struct RebindMenu {
+    pub selected: usize,
+    pub listening: bool,
+}
+
+impl RebindMenu {
+    pub fn action(&self) -> Action {
+        Action::ALL[self.selected]
+    }
+}
+
+/// Builds this frame's [`ActionState`] from the keyboard, gamepads, touch
+/// controls and on-screen buttons. While paused only [`Action::Pause`] gets through.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn gather_actions(
+    mut actions: ResMut<ActionState>,
+    keys: Option<Res<ButtonInput<KeyCode>>>, // absent in headless apps
+    mobile: Option<Res<MobileInputState>>,   // works on native & wasm
+    touch: Option<Res<TouchControls>>,
+    gamepads: Query<&Gamepad>,
+    bindings: Res<InputBindings>,
+    time: Res<Time<Virtual>>,
+    config: Res<AsteroidsConfig>,
+) {
+    let keys = keys.as_deref().filter(|_| config.features.keyboard_input);
+    let mobile = mobile.as_deref();
+    let touch = touch.as_deref();
+    let gamepads = || gamepads.iter().filter(|_| config.features.gamepad_input);
+    let previous = *actions;
+    let mut state = ActionState {
+        held_before: previous.pressed,
+        ..default()
+    };
+    // how hard each action is pressed, from 0 to 1
+    let mut values = [0.0; Action::ALL.len()];
+
+    for action in Action::ALL {
+        if time.is_paused() && action != Action::Pause {
+            continue;
+        }
+
+        let (held, tapped) = match keys {
+            Some(keys) => bindings
+                .keys(action)
+                .fold((false, false), |(held, tapped), key| {
+                    (held || keys.pressed(key), tapped || keys.just_pressed(key))
+                }),
+            None => (false, false),
+        };
+        let (mobile_held, mobile_tapped) = mobile.map_or((false, false), |m| match action {
+            Action::RotateLeft => (m.left, false),
+            Action::RotateRight => (m.right, false),
+            Action::Thrust => (m.up, false),
+            Action::Fire => (m.fire_held, m.fire_just_pressed),
+            Action::Hyperspace => (false, m.hyperspace_just_pressed),
+            Action::Pause => (false, false),
+        });
+        // the touch stick turns and thrusts part way, like a gamepad
+        let (touch_value, touch_tapped) = touch.map_or((0.0, false), |t| match action {
+            Action::RotateLeft => (t.rotation.max(0.0), false),
+            Action::RotateRight => ((-t.rotation).max(0.0), false),
+            Action::Thrust => (t.thrust, false),
+            Action::Fire => (if t.fire_held { 1.0 } else { 0.0 }, t.fire_just_pressed),
+            Action::Hyperspace => (0.0, t.hyperspace_just_pressed),
+            Action::Pause => (0.0, false),
+        });
+        // triggers report how far they are pulled, and stay partway past
+        // the point they count as pressed; other buttons are 0 or 1
+        let mut button_value = 0.0_f32;
+        let mut button_tapped = false;
+        for gamepad in gamepads() {
+            for button in bindings.buttons(action) {
+                let value = match gamepad.get(button) {
+                    Some(value) if value > 0.0 => value,
+                    _ if gamepad.pressed(button) => 1.0,
+                    _ => 0.0,
+                };
+                button_value = button_value.max(value);
+                button_tapped |= gamepad.just_pressed(button);
+            }
+        }
+
+        let value = if held || mobile_held {
+            1.0
+        } else {
+            button_value.max(touch_value)
+        };
+        values[action as usize] = value;
+
+        if value > 0.0 {
+            state.pressed |= action.bit();
+        }
+        if tapped
+            || mobile_tapped
+            || touch_tapped
+            || button_tapped
+            || (state.pressed(action) && !previous.pressed(action))
+        {
+            state.just_pressed |= action.bit();
+        }
+    }
+
+    // a button or key turning wins over the stick
+    let left = values[Action::RotateLeft as usize];
+    let right = values[Action::RotateRight as usize];
+    state.rotation = if left > 0.0 {
+        left
+    } else if right > 0.0 {
+        -right
+    } else if time.is_paused() {
+        0.0
+    } else {
+        let dead_zone = config.gamepad.stick_dead_zone;
+        let stick = gamepads()
+            .map(|gamepad| past_dead_zone(gamepad.left_stick().x, dead_zone))
+            .fold(
+                0.0_f32,
+                |most, x| if x.abs() > most.abs() { x } else { most },
+            );
+        // pushing the stick right turns clockwise
+        -stick
+    };
+    state.thrust = values[Action::Thrust as usize];
+
+    // the stick holds a rotate action like its key would
+    if state.rotation > 0.0 {
+        state.pressed |= Action::RotateLeft.bit();
+    } else if state.rotation < 0.0 {
+        state.pressed |= Action::RotateRight.bit();
+    }
+
+    *actions = state;
+}
+
+/// Freezes the game on [`Action::Pause`] by pausing virtual time, which
+/// stops the fixed step. Only a game in progress can be paused.
+pub(crate) fn toggle_pause(
+    actions: Res<ActionState>,
+    menu: Res<RebindMenu>,
+    state: Res<State<GameState>>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    // the pause key may be the one being rebound
+    if !actions.just_pressed(Action::Pause) || menu.listening {
+        return;
+    }
+
+    if time.is_paused() {
+        time.unpause();
+    } else if matches!(state.get(), GameState::Playing | GameState::Respawning) {
+        time.pause();
+    }
+}
+
+/// While paused, up and down (on the keyboard or a d-pad) pick an action
+/// and enter (or the south button) waits for the key or gamepad button to
+/// bind to it; escape (or select) cancels the wait.
+pub(crate) fn navigate_rebind_menu(
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    gamepads: Query<&Gamepad>,
+    time: Res<Time<Virtual>>,
+    mut menu: ResMut<RebindMenu>,
+    mut bindings: ResMut<InputBindings>,
+) {
+    if !time.is_paused() {
+        return;
+    }
+
+    let key_just_pressed = |key| keys.as_ref().is_some_and(|keys| keys.just_pressed(key));
+    let button_just_pressed = |button| gamepads.iter().any(|gamepad| gamepad.just_pressed(button));
+
+    if menu.listening {
+        let key = keys.as_ref().and_then(|keys| {
+            keys.get_just_pressed()
+                .find(|key| BINDABLE_KEYS.contains(key))
+                .copied()
+        });
+        let button = gamepads.iter().find_map(|gamepad| {
+            gamepad
+                .digital()
+                .get_just_pressed()
+                .find(|button| BINDABLE_BUTTONS.contains(button))
+                .copied()
+        });
+
+        if key_just_pressed(KeyCode::Escape) || button_just_pressed(GamepadButton::Select) {
+            menu.listening = false;
+        } else if let Some(key) = key {
+            bindings.bind(menu.action(), key);
+            menu.listening = false;
+        } else if let Some(button) = button {
+            bindings.bind_button(menu.action(), button);
+            menu.listening = false;
+        }
+        return;
+    }
+
+    let count = Action::ALL.len();
+    if key_just_pressed(KeyCode::ArrowDown) || button_just_pressed(GamepadButton::DPadDown) {
+        menu.selected = (menu.selected + 1) % count;
+    } else if key_just_pressed(KeyCode::ArrowUp) || button_just_pressed(GamepadButton::DPadUp) {
+        menu.selected = (menu.selected + count - 1) % count;
+    } else if key_just_pressed(KeyCode::Enter) || button_just_pressed(GamepadButton::South) {
+        menu.listening = true;
+    }
+}
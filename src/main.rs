@@ -1,42 +1,79 @@
+use asteroidslib::{AsteroidsConfig, InputBindings, Replay};
 
+const USAGE: &str =
+    "usage: asteroids [--seed <u64>] [--bindings <file>] [--record <file> | --replay <file>]";
 
 fn main() {
-    asteroidslib::run();
-}
+    let mut config = AsteroidsConfig::default();
+    let mut args = std::env::args().skip(1);
+    let mut record = None;
+    let mut replay = None;
 
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => {
+                let seed = args.next().and_then(|s| s.parse().ok());
+                let Some(seed) = seed else {
+                    eprintln!("--seed expects an unsigned integer\n{USAGE}");
+                    std::process::exit(2);
+                };
+                config = config.with_seed(seed);
+            }
+            "--bindings" => {
+                let Some(path) = args.next() else {
+                    eprintln!("--bindings expects a file\n{USAGE}");
+                    std::process::exit(2);
+                };
+                let bindings = std::fs::read_to_string(&path)
+                    .map_err(|err| err.to_string())
+                    .and_then(|text| InputBindings::parse(&text).map_err(|err| err.to_string()));
+                match bindings {
+                    Ok(bindings) => config = config.with_bindings(bindings),
+                    Err(err) => {
+                        eprintln!("cannot read bindings from `{path}`: {err}");
+                        std::process::exit(2);
+                    }
+                }
+            }
+            "--record" => {
+                let Some(path) = args.next() else {
+                    eprintln!("--record expects a file\n{USAGE}");
+                    std::process::exit(2);
+                };
+                record = Some(path);
+            }
+            // the replay brings its own seed and config
+            "--replay" => {
+                let Some(path) = args.next() else {
+                    eprintln!("--replay expects a file\n{USAGE}");
+                    std::process::exit(2);
+                };
+                match Replay::load(&path) {
+                    Ok(loaded) => replay = Some(loaded),
+                    Err(err) => {
+                        eprintln!("cannot read replay from `{path}`: {err}");
+                        std::process::exit(2);
+                    }
+                }
+            }
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return;
+            }
+            other => {
+                eprintln!("unknown argument `{other}`\n{USAGE}");
+                std::process::exit(2);
+            }
+        }
+    }
 
-// fn main() {
-//     App::new()
-//         .add_plugins(
-//             DefaultPlugins.set(WindowPlugin {
-//                 primary_window: Some(Window {
-//                     resolution: WindowResolution::new(
-//                         VIEWPORT_WIDTH as u32,
-//                         VIEWPORT_HEIGHT as u32,
-//                     ),
-//                     title: "Asteroids".into(),
-//                     ..default()
-//                 }),
-//                 ..default()
-//             }),
-//         )
-//         .add_message::<ResetGame>()
-//         .add_systems(Startup, setup)
-//         .add_systems(
-//             Update,
-//             (
-//                 keyboard_events,
-//                 decelerate_starship,
-//                 remove_bullet,
-//                 update_position,
-//                 // keep this ordering requirement:
-//                 sync_translate_transform.after(update_position),
-//                 sync_asteroid_scale_transform,
-//                 sync_starship_rotation_transform,
-//                 detect_starship_asteroid_collision,
-//                 detect_bullet_asteroid_collision,
-//                 reset_game,
-//             ),
-//         )
-//         .run();
-// }
+    match (record, replay) {
+        (Some(_), Some(_)) => {
+            eprintln!("--record and --replay cannot be used together\n{USAGE}");
+            std::process::exit(2);
+        }
+        (Some(path), None) => asteroidslib::run_recording(config, path),
+        (None, Some(replay)) => asteroidslib::run_replay(replay),
+        (None, None) => asteroidslib::run_with(config),
+    }
+}
@@ -0,0 +1,129 @@
+use std::sync::{Mutex, OnceLock};
+
+use bevy::prelude::*;
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+#[derive(Default, Clone, Copy)]
+struct VirtualInput {
+    left: bool,
+    right: bool,
+    up: bool,
+    fire: bool, // one-shot
+    fire_held: bool,
+    hyperspace: bool, // one-shot
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn mobile_left_down() {
+    if let Ok(mut v) = virtual_input().lock() {
+        v.left = true;
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn mobile_left_up() {
+    if let Ok(mut v) = virtual_input().lock() {
+        v.left = false;
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn mobile_right_down() {
+    if let Ok(mut v) = virtual_input().lock() {
+        v.right = true;
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn mobile_right_up() {
+    if let Ok(mut v) = virtual_input().lock() {
+        v.right = false;
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn mobile_up_down() {
+    if let Ok(mut v) = virtual_input().lock() {
+        v.up = true;
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn mobile_up_up() {
+    if let Ok(mut v) = virtual_input().lock() {
+        v.up = false;
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn mobile_fire() {
+    if let Ok(mut v) = virtual_input().lock() {
+        v.fire = true; // handled as "just pressed" next frame
+    }
+}
+
+/// Like [`mobile_fire`], but keeps fire held until [`mobile_fire_up`] so the
+/// gun can auto-repeat.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn mobile_fire_down() {
+    if let Ok(mut v) = virtual_input().lock() {
+        v.fire = true;
+        v.fire_held = true;
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn mobile_fire_up() {
+    if let Ok(mut v) = virtual_input().lock() {
+        v.fire_held = false;
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn mobile_hyperspace() {
+    if let Ok(mut v) = virtual_input().lock() {
+        v.hyperspace = true; // handled as "just pressed" next frame
+    }
+}
+
+#[derive(Resource, Default, Clone, Copy)]
+pub struct MobileInputState {
+    pub left: bool,
+    pub right: bool,
+    pub up: bool,
+    pub fire_just_pressed: bool,
+    pub fire_held: bool,
+    pub hyperspace_just_pressed: bool,
+}
+
+pub(crate) fn sync_mobile_input(mut state: ResMut<MobileInputState>) {
+    if let Ok(mut v) = virtual_input().lock() {
+        state.left = v.left;
+        state.right = v.right;
+        state.up = v.up;
+        state.fire_just_pressed = v.fire;
+        state.fire_held = v.fire_held;
+        state.hyperspace_just_pressed = v.hyperspace;
+        // fire and hyperspace are one-shot
+        v.fire = false;
+        v.hyperspace = false;
+    }
+}
+
+static VIRTUAL_INPUT: OnceLock<Mutex<VirtualInput>> = OnceLock::new();
+
+fn virtual_input() -> &'static Mutex<VirtualInput> {
+    VIRTUAL_INPUT.get_or_init(|| Mutex::new(VirtualInput::default()))
+}
@@ -0,0 +1,228 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::components::*;
+use crate::config::AsteroidsConfig;
+use crate::hits::AsteroidDestroyed;
+use crate::hyperspace::ActiveStarship;
+use crate::rng::GameRng;
+use crate::state::ShipDestroyed;
+use crate::systems::ScreenBounds;
+
+/// A short-lived speck of an effect. Particles are pooled: once one burns
+/// out it stays in the world, dead, until the next effect reuses it.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Particle {
+    pub kind: ParticleKind,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub angle: f32,
+    pub spin: f32,
+    pub age: f32,
+    pub lifetime: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ParticleKind {
+    /// A dot thrown out by an exploding asteroid.
+    Spark,
+    /// One edge of the starship's outline, `length` long, tumbling away.
+    Debris { length: f32 },
+    /// A puff from behind the thrusting starship.
+    Exhaust,
+}
+
+impl Particle {
+    pub fn is_alive(&self) -> bool {
+        self.age < self.lifetime
+    }
+
+    /// How much of the particle is left, from 1 when it appears down to 0
+    /// when it burns out.
+    pub fn fade(&self) -> f32 {
+        if self.lifetime > 0.0 {
+            (1.0 - self.age / self.lifetime).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Dead particles waiting to be reused, and how many exist in all.
+#[derive(Resource, Default)]
+pub(crate) struct ParticlePool {
+    free: Vec<Entity>,
+    spawned: usize,
+}
+
+/// Randomness for effects only, kept apart from [`GameRng`] so turning
+/// particles up or down never changes how a seeded game plays out.
+#[derive(Resource)]
+pub(crate) struct ParticleRng(GameRng);
+
+impl ParticleRng {
+    pub(crate) fn new(seed: Option<u64>) -> Self {
+        Self(seed.map_or_else(GameRng::from_entropy, GameRng::new))
+    }
+}
+
+/// Brings a particle to life in a dead pooled entity, or a new one while
+/// the pool is below `max_particles`. Past that the particle is dropped.
+fn emit(commands: &mut Commands, pool: &mut ParticlePool, max: usize, particle: Particle) {
+    if let Some(entity) = pool.free.pop() {
+        commands.entity(entity).insert(particle);
+    } else if pool.spawned < max {
+        pool.spawned += 1;
+        commands.spawn(particle);
+    }
+}
+
+/// A unit vector in a random direction.
+fn random_direction(rng: &mut GameRng) -> Vec2 {
+    Vec2::from_angle(rng.unit() * TAU)
+}
+
+pub(crate) fn update_particles(
+    time: Res<Time>,
+    bounds: Res<ScreenBounds>,
+    mut pool: ResMut<ParticlePool>,
+    mut query: Query<(Entity, &mut Particle)>,
+) {
+    let dt = time.delta_secs();
+
+    for (entity, mut particle) in &mut query {
+        if !particle.is_alive() {
+            continue;
+        }
+
+        particle.age += dt;
+        particle.position = bounds.wrap(particle.position + particle.velocity * dt);
+        particle.angle += particle.spin * dt;
+
+        if !particle.is_alive() {
+            pool.free.push(entity);
+        }
+    }
+}
+
+/// A burst of sparks for each asteroid destroyed, bigger for bigger rocks.
+pub(crate) fn explode_asteroids(
+    mut commands: Commands,
+    mut destroyed: MessageReader<AsteroidDestroyed>,
+    mut pool: ResMut<ParticlePool>,
+    mut rng: ResMut<ParticleRng>,
+    config: Res<AsteroidsConfig>,
+) {
+    let particles = &config.particles;
+
+    for asteroid in destroyed.read() {
+        let (count, speed) = particles.explosion(asteroid.size);
+
+        for _ in 0..count {
+            let velocity = random_direction(&mut rng.0) * speed * rng.0.range(0.2, 1.0);
+            let lifetime = particles.spark_lifetime * rng.0.range(0.5, 1.0);
+
+            emit(
+                &mut commands,
+                &mut pool,
+                particles.max_particles,
+                Particle {
+                    kind: ParticleKind::Spark,
+                    position: asteroid.position,
+                    velocity: asteroid.velocity + velocity,
+                    angle: 0.0,
+                    spin: 0.0,
+                    age: 0.0,
+                    lifetime,
+                },
+            );
+        }
+    }
+}
+
+/// Breaks a destroyed starship into its three outline edges, drifting and
+/// tumbling apart.
+pub(crate) fn scatter_ship_debris(
+    mut commands: Commands,
+    mut destroyed: MessageReader<ShipDestroyed>,
+    mut pool: ResMut<ParticlePool>,
+    mut rng: ResMut<ParticleRng>,
+    config: Res<AsteroidsConfig>,
+) {
+    let particles = &config.particles;
+
+    for ship in destroyed.read() {
+        let rotation = Vec2::from_angle(ship.rotation);
+        let outline = STARSHIP_OUTLINE.map(|vertex| rotation.rotate(vertex * STARSHIP_SIZE));
+
+        for (i, start) in outline.iter().enumerate() {
+            let end = outline[(i + 1) % outline.len()];
+            let middle = start.midpoint(end);
+            let outward = middle.normalize_or_zero();
+
+            emit(
+                &mut commands,
+                &mut pool,
+                particles.max_particles,
+                Particle {
+                    kind: ParticleKind::Debris {
+                        length: start.distance(end),
+                    },
+                    position: ship.position + middle,
+                    velocity: ship.velocity
+                        + outward * particles.debris_speed * rng.0.range(0.5, 1.0),
+                    angle: (end - *start).to_angle(),
+                    spin: rng.0.signed_unit() * 3.0,
+                    age: 0.0,
+                    lifetime: particles.debris_lifetime * rng.0.range(0.75, 1.0),
+                },
+            );
+        }
+    }
+}
+
+/// Puffs exhaust out of the back of the starship while it thrusts, at
+/// `exhaust_rate` per second however long the steps are.
+pub(crate) fn emit_exhaust(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut pool: ResMut<ParticlePool>,
+    mut rng: ResMut<ParticleRng>,
+    mut owed: Local<f32>,
+    config: Res<AsteroidsConfig>,
+    query: Query<(&Starship, &Thrusting, &Position, &Velocity, &Diameter), ActiveStarship>,
+) {
+    let particles = &config.particles;
+
+    for (starship, thrusting, position, velocity, diameter) in &query {
+        if !thrusting.0 {
+            *owed = 0.0;
+            continue;
+        }
+
+        *owed += particles.exhaust_rate * time.delta_secs();
+        let direction = starship.direction();
+        let tail = position.0 - direction * diameter.0 / 2.0;
+
+        while *owed >= 1.0 {
+            *owed -= 1.0;
+            let jitter = direction.perp() * rng.0.signed_unit() * 0.3;
+
+            emit(
+                &mut commands,
+                &mut pool,
+                particles.max_particles,
+                Particle {
+                    kind: ParticleKind::Exhaust,
+                    position: tail,
+                    velocity: velocity.0 - (direction + jitter) * particles.exhaust_speed,
+                    angle: 0.0,
+                    spin: 0.0,
+                    age: 0.0,
+                    lifetime: particles.exhaust_lifetime * rng.0.range(0.5, 1.0),
+                },
+            );
+        }
+    }
+}
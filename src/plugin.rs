@@ -0,0 +1,255 @@
+use bevy::input::gamepad::{GamepadConnectionEvent, GamepadRumbleRequest};
+use bevy::input::InputSystems;
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+
+use crate::config::AsteroidsConfig;
+use crate::gamepad::{handle_gamepad_connections, rumble_on_hits};
+use crate::grid::{rebuild_spatial_grid, SpatialGrid};
+use crate::hits::{resolve_hits, AsteroidDestroyed, Hit};
+use crate::hyperspace::*;
+use crate::input::*;
+use crate::mobile::{sync_mobile_input, MobileInputState};
+use crate::particles::*;
+use crate::rng::{apply_pending_seed, GameRng};
+use crate::saucer::*;
+use crate::score::*;
+use crate::ship::*;
+use crate::state::*;
+use crate::systems::*;
+use crate::touch::{read_touch_controls, TouchControls};
+use crate::wave::*;
+
+/// Ordering hooks for systems added by [`AsteroidsPlugin`], so embedding
+/// apps can slot their own systems before or after the built-in ones.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AsteroidsSet {
+    /// Window bounds and player input, in `PreUpdate`, `Update` and
+    /// `FixedUpdate`.
+    Input,
+    /// Thrust, drag and position integration, in `FixedUpdate`.
+    Movement,
+    /// Collision detection, which only reports [`Hit`](crate::Hit)s, in
+    /// `FixedUpdate`.
+    Collision,
+    /// Applying this step's hits: splits, despawns and scoring, in
+    /// `FixedUpdate`.
+    Resolve,
+    /// Lives, respawning and the rest of the game flow, in `FixedUpdate`.
+    Rules,
+    /// Copying game state into `Transform`s (see
+    /// [`AsteroidsRenderPlugin`](crate::AsteroidsRenderPlugin)).
+    Sync,
+}
+
+/// The game simulation as a plugin. It does not add `DefaultPlugins` or
+/// any rendering, so it can be dropped into an existing `App` or stepped
+/// headless; add [`AsteroidsRenderPlugin`](crate::AsteroidsRenderPlugin)
+/// to draw it, or see [`run`](crate::run) for a standalone setup.
+///
+/// The simulation runs in `FixedUpdate`, so the plugin owns the app's
+/// fixed timestep: it replaces any `Time<Fixed>` already inserted with one
+/// ticking at [`PhysicsConfig::timestep_hz`](crate::PhysicsConfig). Set the
+/// rate there rather than on `Time<Fixed>`.
+#[derive(Default)]
+pub struct AsteroidsPlugin {
+    pub config: AsteroidsConfig,
+}
+
+impl AsteroidsPlugin {
+    pub fn new(config: AsteroidsConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl Plugin for AsteroidsPlugin {
+    fn build(&self, app: &mut App) {
+        let config = self.config.clone();
+        let features = config.features;
+
+        if !app.is_plugin_added::<StatesPlugin>() {
+            app.add_plugins(StatesPlugin);
+        }
+
+        app.insert_resource(ScreenBounds {
+            half_width: config.window.width as f32 / 2.0,
+            half_height: config.window.height as f32 / 2.0,
+        })
+        // shared with every other `FixedUpdate` system in the app
+        .insert_resource(Time::<Fixed>::from_duration(config.physics.timestep()))
+        .insert_resource(config.seed.map_or_else(GameRng::from_entropy, GameRng::new))
+        .insert_resource(Lives(config.rules.starting_lives))
+        .init_resource::<Score>()
+        .init_resource::<SpatialGrid>()
+        .init_resource::<ParticlePool>()
+        .insert_resource(ParticleRng::new(config.seed))
+        .insert_resource(Wave::new(config.waves.starting_wave))
+        .insert_resource(config.bindings.clone())
+        .init_resource::<ActionState>()
+        .init_resource::<RebindMenu>()
+        .insert_state(if features.title_screen {
+            GameState::Title
+        } else {
+            GameState::Playing
+        })
+        .insert_resource(config)
+        .add_message::<ShipDestroyed>()
+        .add_message::<NewGame>()
+        .add_message::<Scored>()
+        .add_message::<WaveStarted>()
+        .add_message::<Hit>()
+        .add_message::<AsteroidDestroyed>()
+        .configure_sets(
+            FixedUpdate,
+            (
+                AsteroidsSet::Input,
+                AsteroidsSet::Movement,
+                AsteroidsSet::Collision,
+                AsteroidsSet::Resolve,
+                AsteroidsSet::Rules,
+            )
+                .chain(),
+        )
+        .configure_sets(Update, (AsteroidsSet::Input, AsteroidsSet::Sync).chain())
+        .add_systems(Startup, setup)
+        // runs before `OnEnter`, which then finds the starship already there
+        .add_systems(
+            OnTransition {
+                exited: GameState::Respawning,
+                entered: GameState::Playing,
+            },
+            respawn_starship,
+        )
+        .add_systems(OnEnter(GameState::Playing), spawn_starship_on_enter)
+        .add_systems(OnEnter(GameState::Respawning), start_respawn_timer)
+        .add_systems(OnEnter(GameState::GameOver), start_game_over_timer)
+        // before the fixed step, so it sees this frame's input
+        .add_systems(
+            PreUpdate,
+            gather_actions
+                .after(InputSystems)
+                .in_set(AsteroidsSet::Input),
+        )
+        .add_systems(
+            Update,
+            (
+                (toggle_pause, navigate_rebind_menu).chain(),
+                (
+                    apply_pending_seed,
+                    start_from_title.run_if(in_state(GameState::Title)),
+                ),
+                (start_new_game, reset_score, reset_wave),
+                (fire_bullet, enter_hyperspace),
+            )
+                .chain()
+                .in_set(AsteroidsSet::Input),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
+                fly_starship.before(update_position),
+                expire_bullets,
+                spin_asteroids,
+                update_position,
+                rebuild_spatial_grid
+                    .after(spin_asteroids)
+                    .after(update_position),
+                update_particles,
+            )
+                .in_set(AsteroidsSet::Movement),
+        )
+        .add_systems(
+            FixedUpdate,
+            // chained so hits are always reported, and resolved, in the same order
+            (
+                detect_starship_asteroid_collision.run_if(in_state(GameState::Playing)),
+                detect_bullet_asteroid_collision,
+            )
+                .chain()
+                .in_set(AsteroidsSet::Collision),
+        )
+        .add_systems(
+            FixedUpdate,
+            (resolve_hits, leave_hyperspace, wear_off_invulnerability)
+                .in_set(AsteroidsSet::Resolve),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
+                // an extra life earned in the same step can still save the game
+                (tally_score, lose_life).chain(),
+                wait_for_clear_centre.run_if(in_state(GameState::Respawning)),
+                leave_game_over.run_if(in_state(GameState::GameOver)),
+                (explode_asteroids, scatter_ship_debris, emit_exhaust).chain(),
+                update_ship_visual_state,
+            )
+                .in_set(AsteroidsSet::Rules),
+        );
+
+        if features.waves {
+            app.add_systems(
+                FixedUpdate,
+                advance_wave
+                    .run_if(in_state(GameState::Playing).or(in_state(GameState::Respawning)))
+                    .in_set(AsteroidsSet::Rules),
+            );
+        }
+
+        if features.saucers {
+            app.insert_resource(SaucerSpawner::new(&self.config))
+                .add_systems(
+                    FixedUpdate,
+                    (
+                        fly_saucers.in_set(AsteroidsSet::Input),
+                        detect_saucer_collisions
+                            .after(detect_bullet_asteroid_collision)
+                            .in_set(AsteroidsSet::Collision),
+                        spawn_saucer
+                            .run_if(in_state(GameState::Playing))
+                            .in_set(AsteroidsSet::Rules),
+                    ),
+                );
+        }
+
+        // the gamepad messages only exist with Bevy's input plugin
+        if features.gamepad_input {
+            app.add_systems(
+                PreUpdate,
+                handle_gamepad_connections
+                    .run_if(resource_exists::<Messages<GamepadConnectionEvent>>)
+                    .in_set(AsteroidsSet::Input)
+                    .after(gather_actions),
+            )
+            .add_systems(
+                Update,
+                rumble_on_hits
+                    .run_if(resource_exists::<Messages<GamepadRumbleRequest>>)
+                    .run_if(|config: Res<AsteroidsConfig>| config.gamepad.rumble)
+                    .in_set(AsteroidsSet::Input),
+            );
+        }
+
+        if features.track_window_size {
+            app.add_systems(Update, update_screen_bounds.in_set(AsteroidsSet::Input));
+        }
+
+        if features.touch_input {
+            app.init_resource::<TouchControls>().add_systems(
+                PreUpdate,
+                read_touch_controls
+                    .after(InputSystems)
+                    .in_set(AsteroidsSet::Input)
+                    .before(gather_actions),
+            );
+        }
+
+        if features.mobile_input {
+            app.init_resource::<MobileInputState>().add_systems(
+                PreUpdate,
+                sync_mobile_input
+                    .in_set(AsteroidsSet::Input)
+                    .before(gather_actions),
+            );
+        }
+    }
+}
@@ -0,0 +1,578 @@
+use bevy::asset::RenderAssetUsages;
+use bevy::math::prelude::Circle;
+use bevy::mesh::{Indices, Mesh, Mesh2d, PrimitiveTopology};
+use bevy::prelude::*;
+use bevy::sprite_render::MeshMaterial2d;
+
+use crate::components::*;
+use crate::config::AsteroidsConfig;
+use crate::particles::{Particle, ParticleKind};
+use crate::plugin::AsteroidsSet;
+use crate::saucer::{Hostile, Saucer};
+use crate::ship::ShipVisualState;
+use crate::state::GameState;
+use crate::systems::ScreenBounds;
+use crate::touch::TouchControls;
+use crate::ui::*;
+
+/// Gives the simulation entities spawned by
+/// [`AsteroidsPlugin`](crate::AsteroidsPlugin) meshes, materials and
+/// `Transform`s. Leave it out to run the game headless.
+pub struct AsteroidsRenderPlugin;
+
+impl Plugin for AsteroidsRenderPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Startup,
+            (
+                load_game_assets,
+                spawn_hud,
+                spawn_camera.run_if(|config: Res<AsteroidsConfig>| config.features.spawn_camera),
+                spawn_touch_stick
+                    .run_if(|config: Res<AsteroidsConfig>| config.features.touch_input),
+            ),
+        )
+        .add_systems(OnEnter(GameState::Title), spawn_title_screen)
+        .add_systems(OnEnter(GameState::GameOver), spawn_game_over_screen)
+        .add_systems(FixedFirst, record_previous_state)
+        .add_systems(
+            Update,
+            (
+                (
+                    attach_starship_visuals,
+                    attach_asteroid_visuals,
+                    attach_bullet_visuals,
+                    attach_saucer_visuals,
+                    attach_particle_visuals,
+                ),
+                attach_ghosts,
+                (
+                    sync_translate_transform,
+                    sync_scale_transform,
+                    sync_starship_rotation_transform,
+                    sync_asteroid_rotation_transform,
+                    sync_starship_visuals,
+                    sync_particles,
+                    sync_touch_stick.run_if(resource_exists::<TouchControls>),
+                    update_hud,
+                    update_wave_banner,
+                    update_pause_menu,
+                ),
+                sync_ghosts,
+            )
+                .chain()
+                .in_set(AsteroidsSet::Sync),
+        );
+    }
+}
+
+/// Simulation position at the start of the current fixed step, so
+/// `Transform`s can be interpolated between steps.
+#[derive(Component)]
+struct PreviousPosition(Vec2);
+
+/// Starship heading or asteroid spin at the start of the current fixed step.
+#[derive(Component)]
+struct PreviousRotation(f32);
+
+/// Entities that wrap around the screen edges and need ghost copies.
+type WrappingFilter = Or<(With<Starship>, With<Asteroid>, With<Saucer>)>;
+
+/// Wrapping entities that were given a mesh this frame.
+type NewlyDrawn = (Added<Mesh2d>, WrappingFilter);
+
+/// A copy of its parent drawn one screen width and/or height away, so an
+/// entity straddling an edge also shows on the opposite side. Every
+/// wrapping entity has three: across the left/right edge, the top/bottom
+/// edge and the corner.
+#[derive(Component)]
+struct Ghost(BVec2);
+
+/// The starship's engine flame, a child drawn behind it while thrusting.
+#[derive(Component)]
+struct Flame;
+
+/// Flames, kept apart from the starships whose visibility is also written.
+type FlameFilter = (With<Flame>, Without<Starship>);
+
+/// The ring the touch stick's knob moves within.
+#[derive(Component)]
+struct StickBase;
+
+/// The touch stick's knob, under the player's thumb.
+#[derive(Component)]
+struct StickKnob;
+
+/// Knobs, kept apart from the stick bases whose transforms are also written.
+type KnobFilter = (With<StickKnob>, Without<StickBase>);
+
+/// Meshes and materials shared by every entity of a kind.
+#[derive(Resource)]
+struct GameAssets {
+    starship_mesh: Handle<Mesh>,
+    starship_material: Handle<ColorMaterial>,
+    flame_mesh: Handle<Mesh>,
+    flame_material: Handle<ColorMaterial>,
+    circle_mesh: Handle<Mesh>,
+    asteroid_material: Handle<ColorMaterial>,
+    bullet_material: Handle<ColorMaterial>,
+    hostile_bullet_material: Handle<ColorMaterial>,
+    saucer_mesh: Handle<Mesh>,
+    saucer_material: Handle<ColorMaterial>,
+    /// A unit square, stretched into a line for ship debris.
+    debris_mesh: Handle<Mesh>,
+}
+
+fn create_starship_mesh() -> Mesh {
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    );
+
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_POSITION,
+        STARSHIP_OUTLINE
+            .iter()
+            .map(|vertex| [vertex.x, vertex.y, 0.0])
+            .collect::<Vec<_>>(),
+    );
+
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; 3]);
+
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_UV_0,
+        vec![[0.5, 0.0], [0.0, 1.0], [1.0, 1.0]],
+    );
+
+    mesh.insert_indices(Indices::U32(vec![0, 1, 2]));
+
+    mesh
+}
+
+/// A triangle fan from the centre of the asteroid out to its outline.
+fn create_asteroid_mesh(shape: &AsteroidShape) -> Mesh {
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    );
+
+    let positions: Vec<[f32; 3]> = std::iter::once(Vec2::ZERO)
+        .chain(shape.0.iter().copied())
+        .map(|vertex| [vertex.x, vertex.y, 0.0])
+        .collect();
+    let uvs: Vec<[f32; 2]> = positions
+        .iter()
+        .map(|[x, y, _]| [x + 0.5, 0.5 - y])
+        .collect();
+    let count = shape.0.len() as u32;
+    let indices = (1..=count).flat_map(|i| [0, i, i % count + 1]).collect();
+
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_NORMAL,
+        vec![[0.0, 0.0, 1.0]; positions.len()],
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.insert_indices(Indices::U32(indices));
+
+    mesh
+}
+
+fn load_game_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.insert_resource(GameAssets {
+        starship_mesh: meshes.add(create_starship_mesh()),
+        starship_material: materials.add(ColorMaterial::from(Color::srgba(1.0, 0.0, 0.0, 1.0))),
+        flame_mesh: meshes.add(Mesh::from(Triangle2d::new(
+            Vec2::new(-0.15, 0.0),
+            Vec2::new(0.0, -0.35),
+            Vec2::new(0.15, 0.0),
+        ))),
+        flame_material: materials.add(ColorMaterial::from(Color::srgba(1.0, 0.6, 0.2, 1.0))),
+        circle_mesh: meshes.add(Mesh::from(Circle::default())),
+        asteroid_material: materials.add(ColorMaterial::from(Color::srgba(0.8, 0.8, 0.8, 1.0))),
+        bullet_material: materials.add(ColorMaterial::from(Color::srgba(1.0, 1.0, 1.0, 1.0))),
+        hostile_bullet_material: materials
+            .add(ColorMaterial::from(Color::srgba(1.0, 0.6, 0.2, 1.0))),
+        saucer_mesh: meshes.add(Mesh::from(Ellipse::new(0.5, 0.25))),
+        saucer_material: materials.add(ColorMaterial::from(Color::srgba(0.4, 1.0, 0.4, 1.0))),
+        debris_mesh: meshes.add(Mesh::from(Rectangle::new(1.0, 1.0))),
+    });
+}
+
+fn spawn_camera(mut commands: Commands) {
+    commands.spawn(Camera2d);
+}
+
+/// The touch stick, drawn over the field and hidden until it is touched.
+/// Both meshes are one unit across, scaled up to the stick's size.
+fn spawn_touch_stick(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let material = materials.add(ColorMaterial::from(Color::srgba(1.0, 1.0, 1.0, 0.3)));
+
+    commands.spawn((
+        StickBase,
+        Mesh2d(meshes.add(Mesh::from(Annulus::new(0.46, 0.5)))),
+        MeshMaterial2d(material.clone()),
+        Transform::from_translation(Vec3::new(0.0, 0.0, 10.0)),
+        Visibility::Hidden,
+    ));
+    commands.spawn((
+        StickKnob,
+        Mesh2d(meshes.add(Mesh::from(Circle::default()))),
+        MeshMaterial2d(material),
+        Transform::from_translation(Vec3::new(0.0, 0.0, 10.0)),
+        Visibility::Hidden,
+    ));
+}
+
+fn attach_starship_visuals(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    query: Query<(Entity, &Starship, &Position), Added<Starship>>,
+) {
+    for (entity, starship, position) in &query {
+        commands
+            .entity(entity)
+            .insert((
+                Mesh2d(assets.starship_mesh.clone()),
+                MeshMaterial2d(assets.starship_material.clone()),
+                Transform::from_translation(Vec3::new(0.0, 0.0, 1.0)),
+                PreviousPosition(position.0),
+                PreviousRotation(starship.rotation_angle),
+            ))
+            .with_child((
+                Flame,
+                Mesh2d(assets.flame_mesh.clone()),
+                MeshMaterial2d(assets.flame_material.clone()),
+                // the base of the flame sits on the back edge of the hull
+                Transform::from_translation(Vec3::new(0.0, -0.5, 0.0)),
+                Visibility::Hidden,
+            ));
+    }
+}
+
+fn attach_asteroid_visuals(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    query: Query<(Entity, &AsteroidShape, &Spin, &Position), Added<Asteroid>>,
+) {
+    for (entity, shape, spin, position) in &query {
+        commands.entity(entity).insert((
+            Mesh2d(meshes.add(create_asteroid_mesh(shape))),
+            MeshMaterial2d(assets.asteroid_material.clone()),
+            Transform::from_translation(Vec3::new(0.0, 0.0, 2.0)),
+            PreviousPosition(position.0),
+            PreviousRotation(spin.angle),
+        ));
+    }
+}
+
+fn attach_bullet_visuals(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    query: Query<(Entity, &Position, Has<Hostile>), Added<Bullet>>,
+) {
+    for (entity, position, hostile) in &query {
+        let material = if hostile {
+            &assets.hostile_bullet_material
+        } else {
+            &assets.bullet_material
+        };
+
+        commands.entity(entity).insert((
+            Mesh2d(assets.circle_mesh.clone()),
+            MeshMaterial2d(material.clone()),
+            Transform::default(),
+            PreviousPosition(position.0),
+        ));
+    }
+}
+
+fn attach_saucer_visuals(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    query: Query<(Entity, &Position), Added<Saucer>>,
+) {
+    for (entity, position) in &query {
+        commands.entity(entity).insert((
+            Mesh2d(assets.saucer_mesh.clone()),
+            MeshMaterial2d(assets.saucer_material.clone()),
+            Transform::from_translation(Vec3::new(0.0, 0.0, 1.0)),
+            PreviousPosition(position.0),
+        ));
+    }
+}
+
+fn particle_color(kind: ParticleKind) -> Color {
+    match kind {
+        ParticleKind::Spark => Color::srgb(0.8, 0.8, 0.8),
+        ParticleKind::Debris { .. } => Color::srgb(1.0, 0.0, 0.0),
+        ParticleKind::Exhaust => Color::srgb(1.0, 0.6, 0.2),
+    }
+}
+
+/// Particles are pooled, so each gets its own material once and keeps it
+/// for every effect it is reused in, fading by its alpha.
+fn attach_particle_visuals(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    query: Query<(Entity, &Particle), Added<Particle>>,
+) {
+    for (entity, particle) in &query {
+        commands.entity(entity).insert((
+            Mesh2d(assets.circle_mesh.clone()),
+            MeshMaterial2d(materials.add(ColorMaterial::from(particle_color(particle.kind)))),
+            Transform::from_translation(Vec3::new(0.0, 0.0, 0.5)),
+        ));
+    }
+}
+
+/// Particles move too little per step to need interpolating, so this just
+/// places, stretches and fades them, and hides the dead ones.
+fn sync_particles(
+    assets: Res<GameAssets>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut query: Query<(
+        &Particle,
+        &mut Mesh2d,
+        &MeshMaterial2d<ColorMaterial>,
+        &mut Transform,
+        &mut Visibility,
+    )>,
+) {
+    for (particle, mut mesh, material, mut transform, mut visibility) in &mut query {
+        if !particle.is_alive() {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        }
+        visibility.set_if_neq(Visibility::Inherited);
+
+        let (shape, scale) = match particle.kind {
+            ParticleKind::Spark => (&assets.circle_mesh, Vec2::splat(3.0)),
+            ParticleKind::Debris { length } => (&assets.debris_mesh, Vec2::new(length, 2.0)),
+            ParticleKind::Exhaust => (&assets.circle_mesh, Vec2::splat(4.0 * particle.fade())),
+        };
+        if mesh.0 != *shape {
+            mesh.0 = shape.clone();
+        }
+        transform.translation = particle.position.extend(transform.translation.z);
+        transform.rotation = Quat::from_rotation_z(particle.angle);
+        transform.scale = scale.extend(1.0);
+
+        if let Some(material) = materials.get_mut(&material.0) {
+            material.color = particle_color(particle.kind).with_alpha(particle.fade());
+        }
+    }
+}
+
+fn record_previous_state(
+    mut positions: Query<(&Position, &mut PreviousPosition)>,
+    mut rotations: Query<(&Starship, &mut PreviousRotation)>,
+    mut spins: Query<(&Spin, &mut PreviousRotation), Without<Starship>>,
+) {
+    for (position, mut previous) in &mut positions {
+        previous.0 = position.0;
+    }
+
+    for (starship, mut previous) in &mut rotations {
+        previous.0 = starship.rotation_angle;
+    }
+
+    for (spin, mut previous) in &mut spins {
+        previous.0 = spin.angle;
+    }
+}
+
+fn sync_translate_transform(
+    fixed_time: Res<Time<Fixed>>,
+    bounds: Res<ScreenBounds>,
+    mut query: Query<(&Position, &PreviousPosition, &mut Transform)>,
+) {
+    let alpha = fixed_time.overstep_fraction();
+    // Anything that moved further than this in one step wrapped around the
+    // screen edge and must not be interpolated across the playfield.
+    let max_step = bounds.half_width.min(bounds.half_height);
+
+    for (position, previous, mut transform) in &mut query {
+        let interpolated = if previous.0.distance(position.0) < max_step {
+            previous.0.lerp(position.0, alpha)
+        } else {
+            position.0
+        };
+
+        transform.translation = interpolated.extend(transform.translation.z);
+    }
+}
+
+fn sync_scale_transform(mut query: Query<(&Diameter, &mut Transform)>) {
+    for (diameter, mut transform) in &mut query {
+        transform.scale = Vec3::splat(diameter.0)
+    }
+}
+
+fn sync_starship_rotation_transform(
+    fixed_time: Res<Time<Fixed>>,
+    mut query: Query<(&Starship, &PreviousRotation, &mut Transform)>,
+) {
+    let alpha = fixed_time.overstep_fraction();
+
+    for (starship, previous, mut transform) in &mut query {
+        let angle = previous.0.lerp(starship.rotation_angle, alpha);
+        transform.rotation = Quat::from_rotation_z(angle);
+    }
+}
+
+fn sync_asteroid_rotation_transform(
+    fixed_time: Res<Time<Fixed>>,
+    mut query: Query<(&Spin, &PreviousRotation, &mut Transform)>,
+) {
+    let alpha = fixed_time.overstep_fraction();
+
+    for (spin, previous, mut transform) in &mut query {
+        let angle = previous.0.lerp(spin.angle, alpha);
+        transform.rotation = Quat::from_rotation_z(angle);
+    }
+}
+
+/// Blinks, fades and lights up the starship as its [`ShipVisualState`]
+/// says. The flame flickers between two lengths while it is on.
+fn sync_starship_visuals(
+    time: Res<Time>,
+    assets: Res<GameAssets>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut starships: Query<(&ShipVisualState, &Children, &mut Visibility), With<Starship>>,
+    mut flames: Query<(&mut Transform, &mut Visibility), FlameFilter>,
+) {
+    for (state, children, mut visibility) in &mut starships {
+        visibility.set_if_neq(if state.visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+
+        for handle in [&assets.starship_material, &assets.flame_material] {
+            if let Some(material) = materials.get_mut(handle) {
+                material.color.set_alpha(state.opacity);
+            }
+        }
+
+        for child in children.iter() {
+            let Ok((mut transform, mut visibility)) = flames.get_mut(child) else {
+                continue;
+            };
+
+            visibility.set_if_neq(if state.flame {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            });
+            let flicker = if ((time.elapsed_secs() * 20.0) as u32).is_multiple_of(2) {
+                1.0
+            } else {
+                0.6
+            };
+            transform.scale = Vec3::new(1.0, flicker, 1.0);
+        }
+    }
+}
+
+/// Shows the touch stick where its touch started, with the knob as far
+/// across it as the touch has been dragged.
+fn sync_touch_stick(
+    controls: Res<TouchControls>,
+    config: Res<AsteroidsConfig>,
+    mut bases: Query<(&mut Transform, &mut Visibility), With<StickBase>>,
+    mut knobs: Query<(&mut Transform, &mut Visibility), KnobFilter>,
+) {
+    let radius = config.touch.stick_radius;
+    let (visibility, centre, knob) = match controls.stick {
+        Some(stick) => (
+            Visibility::Inherited,
+            stick.centre,
+            stick.centre + stick.offset,
+        ),
+        None => (Visibility::Hidden, Vec2::ZERO, Vec2::ZERO),
+    };
+
+    for (mut transform, mut shown) in &mut bases {
+        shown.set_if_neq(visibility);
+        transform.translation = centre.extend(transform.translation.z);
+        transform.scale = Vec3::splat(radius * 2.0);
+    }
+    for (mut transform, mut shown) in &mut knobs {
+        shown.set_if_neq(visibility);
+        transform.translation = knob.extend(transform.translation.z);
+        transform.scale = Vec3::splat(radius * 0.8);
+    }
+}
+
+fn attach_ghosts(
+    mut commands: Commands,
+    query: Query<(Entity, &Mesh2d, &MeshMaterial2d<ColorMaterial>), NewlyDrawn>,
+) {
+    for (entity, mesh, material) in &query {
+        commands.entity(entity).with_children(|parent| {
+            for axes in [
+                BVec2::new(true, false),
+                BVec2::new(false, true),
+                BVec2::TRUE,
+            ] {
+                parent.spawn((
+                    Ghost(axes),
+                    mesh.clone(),
+                    material.clone(),
+                    Transform::default(),
+                    Visibility::Hidden,
+                ));
+            }
+        });
+    }
+}
+
+/// Shows the ghosts of entities within a diameter of an edge, placed one
+/// screen away on the other side.
+fn sync_ghosts(
+    bounds: Res<ScreenBounds>,
+    parents: Query<(&Transform, &Diameter, &Children), (WrappingFilter, Without<Ghost>)>,
+    mut ghosts: Query<(&Ghost, &mut Transform, &mut Visibility)>,
+) {
+    for (parent, diameter, children) in &parents {
+        let position = parent.translation.truncate();
+        let half_extents = Vec2::new(bounds.half_width, bounds.half_height);
+        let margin = half_extents - Vec2::splat(diameter.0);
+        // Which way to shift the copy on each axis, or zero if the entity is
+        // clear of that axis' edges.
+        let shift = Vec2::select(
+            position.abs().cmpgt(margin),
+            -position.signum() * 2.0 * half_extents,
+            Vec2::ZERO,
+        );
+
+        for child in children.iter() {
+            let Ok((ghost, mut transform, mut visibility)) = ghosts.get_mut(child) else {
+                continue;
+            };
+
+            let offset = Vec2::select(ghost.0, shift, Vec2::ZERO);
+            let needed = (!ghost.0 | shift.cmpne(Vec2::ZERO)).all();
+
+            if !needed {
+                visibility.set_if_neq(Visibility::Hidden);
+                continue;
+            }
+
+            // Children live in the parent's rotated and scaled space.
+            transform.translation = parent.rotation.inverse() * (offset.extend(0.0) / parent.scale);
+            visibility.set_if_neq(Visibility::Inherited);
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::{TimeSystems, TimeUpdateStrategy};
+use serde::{Deserialize, Serialize};
+
+use crate::config::AsteroidsConfig;
+use crate::input::ActionState;
+use crate::plugin::AsteroidsSet;
+use crate::rng::GameRng;
+
+/// The replay file format's version, bumped whenever it changes.
+pub const REPLAY_VERSION: u32 = 1;
+
+/// One frame of input: how long it lasted, in nanoseconds of real time,
+/// then what was pressed, just pressed, and how hard to turn and thrust.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+struct Frame(u32, u8, u8, f32, f32);
+
+impl Frame {
+    fn new(delta: Duration, actions: &ActionState) -> Self {
+        // longer frames are cut to `Time<Virtual>`'s max delta anyway
+        let nanos = u32::try_from(delta.as_nanos()).unwrap_or(u32::MAX);
+        Self(
+            nanos,
+            actions.pressed,
+            actions.just_pressed,
+            actions.rotation,
+            actions.thrust,
+        )
+    }
+
+    fn delta(&self) -> Duration {
+        Duration::from_nanos(self.0.into())
+    }
+
+    fn actions(&self, previous: &ActionState) -> ActionState {
+        ActionState {
+            pressed: self.1,
+            just_pressed: self.2,
+            held_before: previous.pressed,
+            rotation: self.3,
+            thrust: self.4,
+        }
+    }
+}
+
+/// A recorded game: the config it was played with, seed included, and
+/// every frame's [`ActionState`] and time step. Played back by
+/// [`InputReplayPlugin`] it reproduces the game exactly. Stored as RON,
+/// with runs of identical frames written once.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Replay {
+    pub version: u32,
+    pub config: AsteroidsConfig,
+    /// Runs of identical frames, as `(count, frame)`.
+    frames: Vec<(u32, Frame)>,
+}
+
+impl Replay {
+    /// An empty recording of a game played with `config`, which should
+    /// have its seed set.
+    pub fn new(config: AsteroidsConfig) -> Self {
+        Self {
+            version: REPLAY_VERSION,
+            config,
+            frames: Vec::new(),
+        }
+    }
+
+    /// How many frames were recorded.
+    pub fn len(&self) -> usize {
+        self.frames.iter().map(|(count, _)| *count as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    fn push(&mut self, frame: Frame) {
+        match self.frames.last_mut() {
+            Some((count, last)) if *last == frame => *count += 1,
+            _ => self.frames.push((1, frame)),
+        }
+    }
+
+    /// Reads a replay in the format [`Display`](fmt::Display) writes,
+    /// checking its version before anything else.
+    pub fn parse(text: &str) -> Result<Self, ReplayError> {
+        #[derive(Deserialize)]
+        struct Header {
+            version: u32,
+        }
+
+        let header: Header = ron::from_str(text)?;
+        if header.version != REPLAY_VERSION {
+            return Err(ReplayError::Version(header.version));
+        }
+        Ok(ron::from_str(text)?)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ReplayError> {
+        std::fs::write(path, self.to_string())?;
+        Ok(())
+    }
+}
+
+/// Writes the replay in the format [`Replay::parse`] reads.
+impl fmt::Display for Replay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&ron::to_string(self).map_err(|_| fmt::Error)?)
+    }
+}
+
+/// Why a replay could not be read or written.
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(std::io::Error),
+    /// Not a replay file.
+    Format(ron::error::SpannedError),
+    /// Recorded in another version of the format.
+    Version(u32),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::Io(err) => write!(f, "{err}"),
+            ReplayError::Format(err) => write!(f, "not a replay: {err}"),
+            ReplayError::Version(version) => {
+                write!(f, "replay version {version}, expected {REPLAY_VERSION}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<std::io::Error> for ReplayError {
+    fn from(err: std::io::Error) -> Self {
+        ReplayError::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for ReplayError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        ReplayError::Format(err)
+    }
+}
+
+/// Records every frame's input into an [`InputRecording`], saved to
+/// `path`, if there is one, when the app exits. Add it after
+/// [`AsteroidsPlugin`](crate::AsteroidsPlugin).
+#[derive(Default)]
+pub struct InputRecorderPlugin {
+    pub path: Option<PathBuf>,
+}
+
+impl Plugin for InputRecorderPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(InputRecording {
+            replay: Replay::new(AsteroidsConfig::default()),
+            path: self.path.clone(),
+        })
+        .add_systems(Startup, start_recording)
+        // once every device, and any script, has had its say
+        .add_systems(Update, record_input.before(AsteroidsSet::Input))
+        .add_systems(Last, save_recording);
+    }
+}
+
+/// The game being recorded by [`InputRecorderPlugin`].
+#[derive(Resource)]
+pub struct InputRecording {
+    pub replay: Replay,
+    path: Option<PathBuf>,
+}
+
+fn start_recording(
+    mut recording: ResMut<InputRecording>,
+    config: Res<AsteroidsConfig>,
+    rng: Res<GameRng>,
+) {
+    // the seed actually used, even if none was configured
+    recording.replay = Replay::new(config.clone().with_seed(rng.seed()));
+}
+
+fn record_input(
+    mut recording: ResMut<InputRecording>,
+    actions: Res<ActionState>,
+    time: Res<Time<Real>>,
+) {
+    recording.replay.push(Frame::new(time.delta(), &actions));
+}
+
+fn save_recording(mut exits: MessageReader<AppExit>, recording: Res<InputRecording>) {
+    if exits.read().count() == 0 {
+        return;
+    }
+    let Some(path) = &recording.path else {
+        return;
+    };
+
+    match recording.replay.save(path) {
+        Ok(()) => info!(
+            "saved {} frames of input to {}",
+            recording.replay.len(),
+            path.display()
+        ),
+        Err(err) => error!("cannot save replay to {}: {err}", path.display()),
+    }
+}
+
+/// Plays a [`Replay`] back in place of live input, stepping the clock by
+/// each frame's recorded time step. The app must be built with the
+/// replay's config. Once it runs out, live input and the usual clock take
+/// over again.
+pub struct InputReplayPlugin {
+    pub replay: Replay,
+}
+
+impl Plugin for InputReplayPlugin {
+    fn build(&self, app: &mut App) {
+        let resume = match app.world().get_resource::<TimeUpdateStrategy>() {
+            Some(TimeUpdateStrategy::ManualDuration(step)) => Some(*step),
+            _ => None,
+        };
+
+        app.insert_resource(InputReplay {
+            replay: self.replay.clone(),
+            run: 0,
+            played: 0,
+            resume,
+        })
+        .add_systems(First, pace_replay.before(TimeSystems))
+        // overrides whatever the devices said
+        .add_systems(PreUpdate, play_back_input.after(AsteroidsSet::Input));
+    }
+}
+
+/// The replay being played by [`InputReplayPlugin`].
+#[derive(Resource)]
+pub struct InputReplay {
+    replay: Replay,
+    /// The run of identical frames being played, and how many of it have
+    /// been.
+    run: usize,
+    played: u32,
+    /// The app's own manual time step, to go back to at the end.
+    resume: Option<Duration>,
+}
+
+impl InputReplay {
+    pub fn finished(&self) -> bool {
+        self.run >= self.replay.frames.len()
+    }
+
+    fn current(&self) -> Option<Frame> {
+        self.replay.frames.get(self.run).map(|(_, frame)| *frame)
+    }
+
+    fn advance(&mut self) {
+        self.played += 1;
+        if self.played == self.replay.frames[self.run].0 {
+            self.run += 1;
+            self.played = 0;
+        }
+    }
+}
+
+fn pace_replay(replay: Res<InputReplay>, mut strategy: ResMut<TimeUpdateStrategy>) {
+    *strategy = match replay.current() {
+        Some(frame) => TimeUpdateStrategy::ManualDuration(frame.delta()),
+        None => replay.resume.map_or(
+            TimeUpdateStrategy::Automatic,
+            TimeUpdateStrategy::ManualDuration,
+        ),
+    };
+}
+
+fn play_back_input(mut replay: ResMut<InputReplay>, mut actions: ResMut<ActionState>) {
+    let Some(frame) = replay.current() else {
+        return;
+    };
+
+    *actions = frame.actions(&actions);
+    replay.advance();
+    if replay.finished() {
+        info!("replay finished");
+    }
+}
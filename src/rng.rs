@@ -0,0 +1,92 @@
+use std::sync::Mutex;
+
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::prelude::*;
+
+use crate::state::NewGame;
+use crate::systems::ScreenBounds;
+
+/// The single source of randomness for the simulation. Every spawn and
+/// split draws from it, so a seed plus the same inputs replays a game.
+#[derive(Resource)]
+pub struct GameRng {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Seeded from the OS, for when no seed was configured.
+    pub fn from_entropy() -> Self {
+        Self::new(rand::random())
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// A value in `[0, 1)`.
+    pub fn unit(&mut self) -> f32 {
+        self.rng.random()
+    }
+
+    /// A value in `[-1, 1)`.
+    pub fn signed_unit(&mut self) -> f32 {
+        self.unit() * 2.0 - 1.0
+    }
+
+    pub fn range(&mut self, min: f32, max: f32) -> f32 {
+        min + self.unit() * (max - min)
+    }
+
+    pub fn random_point(&mut self, bounds: &ScreenBounds) -> Vec2 {
+        Vec2::new(
+            self.signed_unit() * bounds.half_width,
+            self.signed_unit() * bounds.half_height,
+        )
+    }
+
+    /// A point on the border of the screen, away from the centre where the
+    /// starship spawns.
+    pub fn random_edge_point(&mut self, bounds: &ScreenBounds) -> Vec2 {
+        let point = self.random_point(bounds);
+
+        if self.unit() < 0.5 {
+            Vec2::new(bounds.half_width.copysign(point.x), point.y)
+        } else {
+            Vec2::new(point.x, bounds.half_height.copysign(point.y))
+        }
+    }
+}
+
+/// Seed requested from outside the ECS (the wasm API), applied on the next
+/// frame by restarting the game.
+static PENDING_SEED: Mutex<Option<u64>> = Mutex::new(None);
+
+/// Restarts the running game with the given seed. Exported to JS, where
+/// numbers are passed as `u32` so callers do not need a `BigInt`.
+#[cfg_attr(target_arch = "wasm32", wasm_bindgen)]
+pub fn set_seed(seed: u32) {
+    if let Ok(mut pending) = PENDING_SEED.lock() {
+        *pending = Some(seed as u64);
+    }
+}
+
+pub(crate) fn apply_pending_seed(mut rng: ResMut<GameRng>, mut new_game: MessageWriter<NewGame>) {
+    let Some(seed) = PENDING_SEED.lock().ok().and_then(|mut p| p.take()) else {
+        return;
+    };
+
+    *rng = GameRng::new(seed);
+    new_game.write(NewGame);
+}
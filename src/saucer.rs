@@ -0,0 +1,241 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+
+use crate::collision::Collider;
+use crate::components::*;
+use crate::config::AsteroidsConfig;
+use crate::grid::SpatialGrid;
+use crate::hits::Hit;
+use crate::hyperspace::ActiveStarship;
+use crate::rng::GameRng;
+use crate::score::Score;
+use crate::ship::VulnerableStarship;
+use crate::systems::ScreenBounds;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaucerSize {
+    Large,
+    Small,
+}
+
+impl SaucerSize {
+    pub fn scale(&self) -> f32 {
+        match self {
+            SaucerSize::Large => 40.0,
+            SaucerSize::Small => 20.0,
+        }
+    }
+
+    pub fn points(&self) -> u32 {
+        match self {
+            SaucerSize::Large => 200,
+            SaucerSize::Small => 1000,
+        }
+    }
+}
+
+/// A flying saucer crossing the screen. Large ones fire at random, small
+/// ones aim at the starship.
+#[derive(Component)]
+pub struct Saucer {
+    pub size: SaucerSize,
+    /// Horizontal distance left before it leaves the far edge.
+    remaining: f32,
+    turn_timer: Timer,
+    fire_timer: Timer,
+}
+
+/// Marks bullets fired by saucers: they hit the starship and never score.
+#[derive(Component)]
+pub struct Hostile;
+
+/// Time until the next saucer appears.
+#[derive(Resource)]
+pub(crate) struct SaucerSpawner(Timer);
+
+impl SaucerSpawner {
+    pub(crate) fn new(config: &AsteroidsConfig) -> Self {
+        Self(Timer::from_seconds(
+            config.saucers.spawn_interval,
+            TimerMode::Once,
+        ))
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn spawn_saucer(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut spawner: ResMut<SaucerSpawner>,
+    mut rng: ResMut<GameRng>,
+    score: Res<Score>,
+    bounds: Res<ScreenBounds>,
+    config: Res<AsteroidsConfig>,
+    saucers: Query<(), With<Saucer>>,
+) {
+    if !saucers.is_empty() || !spawner.0.tick(time.delta()).is_finished() {
+        return;
+    }
+
+    let saucers_config = &config.saucers;
+
+    // Small saucers become more common as the score climbs.
+    let small_chance =
+        (0.2 + 0.6 * score.0 as f32 / saucers_config.small_saucer_score as f32).min(0.8);
+    let size = if rng.unit() < small_chance {
+        SaucerSize::Small
+    } else {
+        SaucerSize::Large
+    };
+    let speed = match size {
+        SaucerSize::Large => saucers_config.large_speed,
+        SaucerSize::Small => saucers_config.small_speed,
+    };
+
+    let diameter = size.scale();
+    let direction = if rng.unit() < 0.5 { 1.0 } else { -1.0 };
+    let start = Vec2::new(
+        -direction * bounds.half_width,
+        rng.signed_unit() * bounds.half_height * 0.8,
+    );
+
+    commands.spawn((
+        Saucer {
+            size,
+            remaining: 2.0 * bounds.half_width,
+            turn_timer: Timer::from_seconds(saucers_config.turn_interval, TimerMode::Repeating),
+            fire_timer: Timer::from_seconds(saucers_config.fire_interval, TimerMode::Repeating),
+        },
+        Position(start),
+        Velocity(Vec2::new(direction * speed, 0.0)),
+        Diameter(diameter),
+    ));
+
+    spawner.0.reset();
+}
+
+/// Zig-zags saucers, fires their guns and removes them once they have
+/// crossed the screen.
+pub(crate) fn fly_saucers(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut rng: ResMut<GameRng>,
+    score: Res<Score>,
+    config: Res<AsteroidsConfig>,
+    mut saucers: Query<(Entity, &mut Saucer, &Position, &mut Velocity)>,
+    starship: Query<&Position, ActiveStarship>,
+) {
+    let saucers_config = &config.saucers;
+
+    for (entity, mut saucer, position, mut velocity) in &mut saucers {
+        saucer.remaining -= velocity.0.x.abs() * time.delta_secs();
+        if saucer.remaining <= 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        if saucer.turn_timer.tick(time.delta()).just_finished() {
+            let heading = (rng.unit() * 3.0).floor() - 1.0; // -1, 0 or 1
+            velocity.0.y = heading * velocity.0.x.abs();
+        }
+
+        if !saucer.fire_timer.tick(time.delta()).just_finished() {
+            continue;
+        }
+
+        let aim = match (saucer.size, starship.single()) {
+            (SaucerSize::Small, Ok(target)) => {
+                // Aim gets sharper as the score climbs.
+                let skill = (score.0 as f32 / saucers_config.perfect_aim_score as f32).min(1.0);
+                let error = rng.signed_unit() * (1.0 - skill) * PI / 4.0;
+                Vec2::from_angle(error).rotate((target.0 - position.0).normalize_or(Vec2::X))
+            }
+            _ => Vec2::from_angle(rng.unit() * 2.0 * PI),
+        };
+
+        commands.spawn((
+            Bullet,
+            Hostile,
+            Position(position.0),
+            Velocity(aim * saucers_config.bullet_velocity),
+            Diameter(BULLET_SIZE),
+        ));
+    }
+}
+
+/// Saucers against the player's bullets, the starship and asteroids, and
+/// saucer bullets against the starship.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn detect_saucer_collisions(
+    time: Res<Time>,
+    bounds: Res<ScreenBounds>,
+    grid: Res<SpatialGrid>,
+    saucers: Query<(Entity, &Diameter, &Position), With<Saucer>>,
+    bullets: Query<(Entity, &Position, &Velocity, Has<Hostile>), With<Bullet>>,
+    starship: Query<(Entity, &Starship, &Diameter, &Position), VulnerableStarship>,
+    asteroids: Query<&Position, With<Asteroid>>,
+    mut hits: MessageWriter<Hit>,
+) {
+    let dt = time.delta_secs();
+    let starship = starship
+        .single()
+        .ok()
+        .map(|(entity, starship, size, position)| {
+            (
+                entity,
+                position,
+                Collider::starship(starship, position, size),
+            )
+        });
+
+    if let Some((starship_entity, _, starship_collider)) = &starship {
+        for (bullet_entity, bullet_position, bullet_velocity, hostile) in &bullets {
+            let bullet_collider = Collider::bullet(bullet_position, bullet_velocity, dt);
+
+            if hostile && starship_collider.overlaps_wrapped(&bullet_collider, &bounds) {
+                hits.write(Hit {
+                    attacker: bullet_entity,
+                    target: *starship_entity,
+                    direction: bullet_velocity.0,
+                });
+            }
+        }
+    }
+
+    for (saucer_entity, saucer_size, saucer_position) in &saucers {
+        let saucer_collider = Collider::circle(saucer_position.0, saucer_size.0 / 2.0);
+
+        for (bullet_entity, bullet_position, bullet_velocity, hostile) in &bullets {
+            let bullet_collider = Collider::bullet(bullet_position, bullet_velocity, dt);
+
+            if !hostile && saucer_collider.overlaps_wrapped(&bullet_collider, &bounds) {
+                hits.write(Hit {
+                    attacker: bullet_entity,
+                    target: saucer_entity,
+                    direction: bullet_velocity.0,
+                });
+            }
+        }
+
+        if let Some((starship_entity, starship_position, starship_collider)) = &starship {
+            if saucer_collider.overlaps_wrapped(starship_collider, &bounds) {
+                hits.write(Hit {
+                    attacker: *starship_entity,
+                    target: saucer_entity,
+                    direction: bounds.wrapped_delta(starship_position.0, saucer_position.0),
+                });
+            }
+        }
+
+        for asteroid_entity in grid.overlapping(&saucer_collider, &bounds) {
+            if let Ok(asteroid_position) = asteroids.get(asteroid_entity) {
+                hits.write(Hit {
+                    attacker: saucer_entity,
+                    target: asteroid_entity,
+                    direction: bounds.wrapped_delta(saucer_position.0, asteroid_position.0),
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+
+use crate::config::AsteroidsConfig;
+use crate::state::{Lives, NewGame};
+
+/// Points earned in the current game.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Score(pub u32);
+
+/// Sent whenever the player earns points, before they are added to
+/// [`Score`].
+#[derive(Message, Clone, Copy, Debug)]
+pub struct Scored {
+    pub points: u32,
+    /// Where the points were earned, e.g. the destroyed asteroid.
+    pub position: Vec2,
+}
+
+/// Adds scored points and hands out an extra life every
+/// `rules.extra_life_every` points.
+pub(crate) fn tally_score(
+    mut scored: MessageReader<Scored>,
+    mut score: ResMut<Score>,
+    mut lives: ResMut<Lives>,
+    config: Res<AsteroidsConfig>,
+) {
+    let threshold = config.rules.extra_life_every;
+
+    for event in scored.read() {
+        let before = score.0;
+        score.0 = score.0.saturating_add(event.points);
+
+        if let (Some(after), Some(before)) = (
+            score.0.checked_div(threshold),
+            before.checked_div(threshold),
+        ) {
+            lives.0 += after - before;
+        }
+    }
+}
+
+pub(crate) fn reset_score(mut new_game: MessageReader<NewGame>, mut score: ResMut<Score>) {
+    if new_game.read().next().is_some() {
+        score.0 = 0;
+    }
+}
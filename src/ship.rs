@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+
+use crate::components::*;
+use crate::config::AsteroidsConfig;
+use crate::hyperspace::InHyperspace;
+use crate::systems::spawn_starship;
+
+/// Put on a respawned starship: nothing can hit it, and it cannot hit
+/// anything, until the timer runs out.
+#[derive(Component)]
+pub struct Invulnerable(Timer);
+
+/// Matches the starship while it can be hit.
+pub(crate) type VulnerableStarship = (With<Starship>, Without<InHyperspace>, Without<Invulnerable>);
+
+/// What the starship should look like, worked out by the simulation so a
+/// renderer only has to copy it.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct ShipVisualState {
+    /// The engine is firing: draw the flame.
+    pub flame: bool,
+    /// Off during the dark half of an invulnerability blink.
+    pub visible: bool,
+    /// From 0 to 1; the starship fades in at its destination while in
+    /// hyperspace.
+    pub opacity: f32,
+}
+
+impl Default for ShipVisualState {
+    fn default() -> Self {
+        Self {
+            flame: false,
+            visible: true,
+            opacity: 1.0,
+        }
+    }
+}
+
+/// Brings the starship back after a loss, or at the start of a game,
+/// protected for `rules.respawn_invulnerability` seconds.
+pub(crate) fn respawn_starship(mut commands: Commands, config: Res<AsteroidsConfig>) {
+    let starship = spawn_starship(&mut commands);
+    let duration = config.rules.respawn_invulnerability;
+
+    if duration > 0.0 {
+        commands
+            .entity(starship)
+            .insert(Invulnerable(Timer::from_seconds(duration, TimerMode::Once)));
+    }
+}
+
+pub(crate) fn wear_off_invulnerability(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Invulnerable)>,
+) {
+    for (entity, mut invulnerable) in &mut query {
+        if invulnerable.0.tick(time.delta()).is_finished() {
+            commands.entity(entity).remove::<Invulnerable>();
+        }
+    }
+}
+
+pub(crate) fn update_ship_visual_state(
+    config: Res<AsteroidsConfig>,
+    mut query: Query<(
+        &Thrusting,
+        Option<&Invulnerable>,
+        Option<&InHyperspace>,
+        &mut ShipVisualState,
+    )>,
+) {
+    for (thrusting, invulnerable, hyperspace, mut state) in &mut query {
+        let blinks = (config.rules.blink_interval > 0.0).then_some(config.rules.blink_interval);
+        let visible = match (invulnerable, blinks) {
+            (Some(invulnerable), Some(interval)) => {
+                ((invulnerable.0.elapsed_secs() / interval) as u32).is_multiple_of(2)
+            }
+            _ => true,
+        };
+
+        state.set_if_neq(ShipVisualState {
+            flame: thrusting.0 && hyperspace.is_none(),
+            visible,
+            opacity: hyperspace.map_or(1.0, InHyperspace::progress),
+        });
+    }
+}
@@ -0,0 +1,156 @@
+use bevy::prelude::*;
+
+use crate::components::*;
+use crate::config::AsteroidsConfig;
+use crate::input::{Action, ActionState};
+use crate::rng::GameRng;
+use crate::saucer::Saucer;
+use crate::systems::{spawn_field, spawn_starship, ScreenBounds};
+
+/// Top-level flow of a game.
+#[derive(States, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameState {
+    /// Asteroids drift behind the title until the player presses fire.
+    #[default]
+    Title,
+    /// The starship is alive and under player control.
+    Playing,
+    /// The starship was destroyed (or a game just started) and is waiting
+    /// for the centre of the screen to clear.
+    Respawning,
+    /// No lives left; returns to the title after a short delay.
+    GameOver,
+}
+
+/// Remaining starships, including the one currently in play.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Lives(pub u32);
+
+/// Counts down the minimum time spent in `Respawning` and `GameOver`.
+#[derive(Resource)]
+pub(crate) struct StateTimer(Timer);
+
+/// Sent when the starship is destroyed, by a hit or a failed hyperspace
+/// jump.
+#[derive(Message, Clone, Copy, Debug)]
+pub struct ShipDestroyed {
+    pub position: Vec2,
+    pub velocity: Vec2,
+    /// The starship's heading, as in [`Starship::rotation_angle`].
+    pub rotation: f32,
+}
+
+/// Clears the field and starts over with full lives.
+#[derive(Message)]
+pub struct NewGame;
+
+/// Matches every entity that belongs to a running game.
+type GameplayFilter = Or<(With<Starship>, With<Bullet>, With<Asteroid>, With<Saucer>)>;
+
+pub(crate) fn spawn_starship_on_enter(mut commands: Commands, query: Query<(), With<Starship>>) {
+    if query.is_empty() {
+        spawn_starship(&mut commands);
+    }
+}
+
+pub(crate) fn start_respawn_timer(mut commands: Commands, config: Res<AsteroidsConfig>) {
+    commands.insert_resource(StateTimer(Timer::from_seconds(
+        config.rules.respawn_delay,
+        TimerMode::Once,
+    )));
+}
+
+pub(crate) fn start_game_over_timer(mut commands: Commands, config: Res<AsteroidsConfig>) {
+    commands.insert_resource(StateTimer(Timer::from_seconds(
+        config.rules.game_over_delay,
+        TimerMode::Once,
+    )));
+}
+
+pub(crate) fn lose_life(
+    mut destroyed: MessageReader<ShipDestroyed>,
+    mut lives: ResMut<Lives>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if destroyed.read().next().is_none() {
+        return;
+    }
+
+    lives.0 = lives.0.saturating_sub(1);
+
+    if lives.0 > 0 {
+        next_state.set(GameState::Respawning);
+    } else {
+        next_state.set(GameState::GameOver);
+    }
+}
+
+/// Brings the starship back once the delay has passed and no asteroid is
+/// within `respawn_clear_radius` of the centre.
+pub(crate) fn wait_for_clear_centre(
+    time: Res<Time>,
+    mut timer: ResMut<StateTimer>,
+    config: Res<AsteroidsConfig>,
+    asteroids: Query<(&Position, &Diameter), With<Asteroid>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if !timer.0.tick(time.delta()).is_finished() {
+        return;
+    }
+
+    let clear_radius = config.rules.respawn_clear_radius;
+    let centre_is_clear = asteroids
+        .iter()
+        .all(|(position, diameter)| position.0.length() > clear_radius + diameter.0 / 2.0);
+
+    if centre_is_clear {
+        next_state.set(GameState::Playing);
+    }
+}
+
+pub(crate) fn leave_game_over(
+    time: Res<Time>,
+    mut timer: ResMut<StateTimer>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if timer.0.tick(time.delta()).is_finished() {
+        next_state.set(GameState::Title);
+    }
+}
+
+pub(crate) fn start_from_title(actions: Res<ActionState>, mut new_game: MessageWriter<NewGame>) {
+    if actions.just_pressed(Action::Fire) {
+        new_game.write(NewGame);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn start_new_game(
+    mut new_game: MessageReader<NewGame>,
+    mut commands: Commands,
+    mut rng: ResMut<GameRng>,
+    mut lives: ResMut<Lives>,
+    mut next_state: ResMut<NextState<GameState>>,
+    bounds: Res<ScreenBounds>,
+    config: Res<AsteroidsConfig>,
+    to_clear: Query<Entity, GameplayFilter>,
+) {
+    // Read messages; if none, do nothing this frame
+    if new_game.read().next().is_none() {
+        return;
+    }
+
+    for entity in &to_clear {
+        commands.entity(entity).despawn();
+    }
+
+    spawn_field(
+        &mut commands,
+        &mut rng,
+        &bounds,
+        &config,
+        config.waves.starting_wave,
+    );
+    lives.0 = config.rules.starting_lives;
+    next_state.set(GameState::Respawning);
+}
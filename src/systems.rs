@@ -0,0 +1,375 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::collision::Collider;
+use crate::components::*;
+use crate::config::AsteroidsConfig;
+use crate::grid::SpatialGrid;
+use crate::hits::Hit;
+use crate::hyperspace::InHyperspace;
+use crate::input::{Action, ActionState};
+use crate::rng::GameRng;
+use crate::saucer::Hostile;
+use crate::ship::VulnerableStarship;
+
+/// Half extents of the playfield, kept in sync with the primary window.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct ScreenBounds {
+    pub half_width: f32,
+    pub half_height: f32,
+}
+
+impl ScreenBounds {
+    /// Brings a point that has left the playfield back in from the
+    /// opposite edge.
+    pub fn wrap(&self, point: Vec2) -> Vec2 {
+        Vec2::new(
+            wrap_axis(point.x, self.half_width),
+            wrap_axis(point.y, self.half_height),
+        )
+    }
+
+    /// The shortest offset from `from` to `to` on the wrapping playfield,
+    /// which may cross an edge.
+    pub fn wrapped_delta(&self, from: Vec2, to: Vec2) -> Vec2 {
+        self.wrap(to - from)
+    }
+}
+
+fn wrap_axis(value: f32, half: f32) -> f32 {
+    if value.abs() <= half {
+        value // leave points on the field untouched, bit for bit
+    } else {
+        (value + half).rem_euclid(2.0 * half) - half
+    }
+}
+
+impl Default for ScreenBounds {
+    fn default() -> Self {
+        Self {
+            half_width: 640.0, // fallback before first update
+            half_height: 360.0,
+        }
+    }
+}
+
+pub(crate) fn update_screen_bounds(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut bounds: ResMut<ScreenBounds>,
+) {
+    if let Ok(window) = windows.single() {
+        bounds.half_width = window.width() / 2.0;
+        bounds.half_height = window.height() / 2.0;
+    }
+}
+
+pub(crate) fn spawn_starship(commands: &mut Commands) -> Entity {
+    commands
+        .spawn((
+            Starship {
+                rotation_angle: 0.0,
+            },
+            Position(Vec2::ZERO),
+            Velocity(Vec2::ZERO),
+            Diameter(STARSHIP_SIZE),
+        ))
+        .id()
+}
+
+fn spawn_asteroid(
+    commands: &mut Commands,
+    rng: &mut GameRng,
+    config: &AsteroidsConfig,
+    size: AsteroidSize,
+    position: Vec2,
+    velocity: Vec2,
+) {
+    commands.spawn((
+        Asteroid { size },
+        AsteroidShape::random(rng),
+        Spin {
+            angle: rng.unit() * 2.0 * PI,
+            rate: rng.signed_unit() * config.physics.asteroid_max_spin,
+        },
+        Position(position),
+        Velocity(velocity),
+        Diameter(size.scale()),
+    ));
+}
+
+pub(crate) fn spawn_field(
+    commands: &mut Commands,
+    rng: &mut GameRng,
+    bounds: &ScreenBounds,
+    config: &AsteroidsConfig,
+    wave: u32,
+) {
+    for _ in 0..config.wave_asteroid_count(wave) {
+        let pos = rng.random_edge_point(bounds);
+        let vel_dir = rng.random_point(bounds).normalize();
+
+        spawn_asteroid(
+            commands,
+            rng,
+            config,
+            AsteroidSize::Big,
+            pos,
+            vel_dir * config.wave_asteroid_velocity(wave),
+        );
+    }
+}
+
+pub(crate) fn setup(
+    mut commands: Commands,
+    mut rng: ResMut<GameRng>,
+    bounds: Res<ScreenBounds>,
+    config: Res<AsteroidsConfig>,
+) {
+    spawn_field(
+        &mut commands,
+        &mut rng,
+        &bounds,
+        &config,
+        config.waves.starting_wave,
+    );
+}
+
+/// Moves everything by its velocity. The playfield wraps around: anything
+/// whose centre crosses an edge comes back in from the opposite one.
+pub(crate) fn update_position(
+    time: Res<Time>,
+    bounds: Res<ScreenBounds>,
+    mut query: Query<(&Velocity, &mut Position)>,
+) {
+    for (velocity, mut position) in &mut query {
+        position.0 = bounds.wrap(position.0 + velocity.0 * time.delta_secs());
+    }
+}
+
+pub(crate) fn spin_asteroids(time: Res<Time>, mut query: Query<&mut Spin>) {
+    for mut spin in &mut query {
+        spin.angle += spin.rate * time.delta_secs();
+    }
+}
+
+/// The starship's controls: turns and accelerates it as hard as
+/// [`ActionState`] says, so a half-pushed stick or trigger gives half the
+/// rate, and lets drag slow it whenever it is not thrusting. Both read the
+/// same thrust, so every input device flies the starship alike. In
+/// hyperspace it cannot be steered, but drag still applies.
+pub(crate) fn fly_starship(
+    time: Res<Time>,
+    actions: Res<ActionState>,
+    mut query: Query<(
+        &mut Starship,
+        &mut Velocity,
+        &mut Thrusting,
+        Has<InHyperspace>,
+    )>,
+    config: Res<AsteroidsConfig>,
+) {
+    let physics = &config.physics;
+    let dt = time.delta_secs();
+    let drag = (-physics.starship_deceleration * dt).exp();
+
+    for (mut starship, mut velocity, mut thrusting, in_hyperspace) in &mut query {
+        let thrust = if in_hyperspace { 0.0 } else { actions.thrust() };
+        if !in_hyperspace {
+            starship.rotation_angle += physics.starship_rotation_speed * actions.rotation() * dt;
+        }
+
+        thrusting.0 = thrust > 0.0;
+        if thrusting.0 {
+            velocity.0 += starship.direction() * physics.starship_acceleration * thrust * dt;
+
+            if velocity.0.length() > physics.starship_max_velocity {
+                velocity.0 = velocity.0.normalize_or_zero() * physics.starship_max_velocity;
+            }
+        } else {
+            velocity.0 *= drag;
+        }
+    }
+}
+
+/// Spawns bullets from the starship's nose on the frame fire is pressed,
+/// and again every `bullets.auto_fire_interval` while it is held if
+/// auto-fire is on. Runs in `Update` so a one-frame press is never missed
+/// or repeated by the fixed step.
+pub(crate) fn fire_bullet(
+    mut commands: Commands,
+    time: Res<Time>,
+    actions: Res<ActionState>,
+    mut query: Query<(&Starship, &Position, &Velocity, &Diameter, &mut Gun), Without<InHyperspace>>,
+    bullets: Query<(), (With<Bullet>, Without<Hostile>)>,
+    config: Res<AsteroidsConfig>,
+) {
+    let gun_config = &config.bullets;
+    let just_pressed = actions.just_pressed(Action::Fire);
+    let held = actions.pressed(Action::Fire);
+    let in_flight = bullets.iter().count();
+
+    for (starship, starship_position, starship_velocity, diameter, mut gun) in &mut query {
+        gun.since_last_shot += time.delta_secs();
+
+        let repeat =
+            gun_config.auto_fire && held && gun.since_last_shot >= gun_config.auto_fire_interval;
+        if !(just_pressed || repeat)
+            || gun.since_last_shot < gun_config.cooldown
+            || in_flight >= gun_config.max_on_screen
+        {
+            continue;
+        }
+
+        let nose = starship.nose(starship_position, diameter);
+        let mut velocity = starship.direction().normalize() * config.physics.bullet_velocity;
+        if gun_config.inherit_ship_velocity {
+            velocity += starship_velocity.0;
+        }
+
+        gun.since_last_shot = 0.0;
+        commands.spawn((
+            Bullet,
+            Position(nose),
+            Velocity(velocity),
+            Diameter(BULLET_SIZE),
+        ));
+    }
+}
+
+/// Ages bullets by how far they moved this step and removes those past
+/// `bullets.lifetime`.
+pub(crate) fn expire_bullets(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut query: Query<(Entity, &Velocity, &mut Travelled), With<Bullet>>,
+    config: Res<AsteroidsConfig>,
+) {
+    let dt = time.delta_secs();
+
+    for (entity, velocity, mut travelled) in &mut query {
+        travelled.distance += velocity.0.length() * dt;
+        travelled.seconds += dt;
+
+        if config
+            .bullets
+            .lifetime
+            .expired(travelled.distance, travelled.seconds)
+        {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+pub(crate) fn detect_starship_asteroid_collision(
+    starship_query: Query<(Entity, &Starship, &Diameter, &Position), VulnerableStarship>,
+    grid: Res<SpatialGrid>,
+    bounds: Res<ScreenBounds>,
+    mut hits: MessageWriter<Hit>,
+) {
+    for (starship_entity, starship, starship_size, starship_position) in &starship_query {
+        let starship_collider = Collider::starship(starship, starship_position, starship_size);
+
+        // only need one hit
+        if let Some(asteroid) = grid.overlapping(&starship_collider, &bounds).first() {
+            hits.write(Hit {
+                attacker: *asteroid,
+                target: starship_entity,
+                direction: Vec2::ZERO,
+            });
+        }
+    }
+}
+
+/// An asteroid that has just been hit.
+pub(crate) struct Impact {
+    pub entity: Entity,
+    pub size: AsteroidSize,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    /// Which way the blow travelled; fragments fan out across it.
+    pub direction: Vec2,
+}
+
+/// Despawns an asteroid and spawns its fragments, if it has any. The
+/// fragments keep the parent's velocity and spread evenly to either side of
+/// the impact, so on average they carry on as the parent did. Each one gets
+/// a shape and spin of its own.
+pub(crate) fn split_asteroid(
+    commands: &mut Commands,
+    rng: &mut GameRng,
+    bounds: &ScreenBounds,
+    config: &AsteroidsConfig,
+    wave_speed: f32,
+    impact: Impact,
+) {
+    commands.entity(impact.entity).despawn();
+
+    let Some(new_size) = impact.size.split() else {
+        return;
+    };
+    let count = config.splits.fragments(impact.size);
+    let direction = impact
+        .direction
+        .try_normalize()
+        .unwrap_or_else(|| Vec2::from_angle(rng.unit() * 2.0 * PI));
+    // Tilt and speed vary per split, but are shared by all the fragments
+    // so their spreads still cancel out.
+    let across = Vec2::from_angle(rng.signed_unit() * PI / 12.0).rotate(direction.perp());
+    let spread = wave_speed * config.splits.spread(new_size) * rng.range(0.8, 1.2);
+
+    for i in 0..count {
+        // from -1 to 1 across the impact
+        let side = if count > 1 {
+            2.0 * i as f32 / (count - 1) as f32 - 1.0
+        } else {
+            0.0
+        };
+
+        spawn_asteroid(
+            commands,
+            rng,
+            config,
+            new_size,
+            bounds.wrap(impact.position + across * side * new_size.scale() / 2.0),
+            impact.velocity + across * side * spread,
+        );
+    }
+}
+
+/// Reports every asteroid each bullet touched this step, nearest to where
+/// the bullet started first, so it hits the first rock in its path.
+pub(crate) fn detect_bullet_asteroid_collision(
+    time: Res<Time>,
+    bullets_query: Query<(Entity, &Position, &Velocity), With<Bullet>>,
+    asteroids_query: Query<&Position, With<Asteroid>>,
+    grid: Res<SpatialGrid>,
+    bounds: Res<ScreenBounds>,
+    mut hits: MessageWriter<Hit>,
+) {
+    let dt = time.delta_secs();
+
+    for (bullet_entity, bullet_position, bullet_velocity) in &bullets_query {
+        let bullet_collider = Collider::bullet(bullet_position, bullet_velocity, dt);
+        let start = bullet_position.0 - bullet_velocity.0 * dt;
+        let distance = |entity: &Entity| {
+            asteroids_query
+                .get(*entity)
+                .map_or(f32::INFINITY, |position| {
+                    bounds.wrapped_delta(start, position.0).length_squared()
+                })
+        };
+
+        let mut targets = grid.overlapping(&bullet_collider, &bounds);
+        targets.sort_by(|a, b| distance(a).total_cmp(&distance(b)));
+
+        for target in targets {
+            hits.write(Hit {
+                attacker: bullet_entity,
+                target,
+                direction: bullet_velocity.0,
+            });
+        }
+    }
+}
@@ -0,0 +1,151 @@
+use bevy::prelude::*;
+
+use crate::components::{Position, Starship};
+use crate::config::AsteroidsConfig;
+use crate::input::past_dead_zone;
+use crate::systems::ScreenBounds;
+
+/// A virtual stick, floating wherever its touch started on the left half
+/// of the screen. Positions are in world coordinates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TouchStick {
+    /// The touch dragging it.
+    pub id: u64,
+    /// Where the touch started.
+    pub centre: Vec2,
+    /// How far the touch has been dragged from `centre`, no further than
+    /// the stick's radius.
+    pub offset: Vec2,
+}
+
+/// The touch controls, followed from Bevy's `Touches`: a stick on the left
+/// half that points the starship and thrusts, and the right half to fire
+/// with a tap, auto-fire while held, or jump to hyperspace with a flick
+/// upwards.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct TouchControls {
+    pub stick: Option<TouchStick>,
+    /// The touch held down on the right half.
+    pub trigger: Option<u64>,
+    /// How long `trigger` has been held, in seconds.
+    pub(crate) trigger_time: f32,
+    /// With [`TouchConfig::aim_at_tap`](crate::TouchConfig), the point the
+    /// starship is turning towards to shoot at.
+    pub aim: Option<Vec2>,
+    pub(crate) rotation: f32,
+    pub(crate) thrust: f32,
+    pub(crate) fire_held: bool,
+    pub(crate) fire_just_pressed: bool,
+    pub(crate) hyperspace_just_pressed: bool,
+}
+
+/// Follows this frame's touches and works out how hard to turn and thrust
+/// for [`gather_actions`](crate::input) to merge with the other devices.
+/// Turning is proportional to how far the starship is from the heading
+/// wanted, so it settles on it instead of overshooting.
+pub(crate) fn read_touch_controls(
+    touches: Option<Res<Touches>>, // absent without the input plugin
+    mut controls: ResMut<TouchControls>,
+    starships: Query<(&Starship, &Position)>,
+    bounds: Res<ScreenBounds>,
+    time: Res<Time>,
+    config: Res<AsteroidsConfig>,
+) {
+    let Some(touches) = touches else {
+        return;
+    };
+    let touch = &config.touch;
+    // touches are in window coordinates, from the top-left corner
+    let to_world =
+        |point: Vec2| Vec2::new(point.x - bounds.half_width, bounds.half_height - point.y);
+    let starship = starships.single().ok();
+
+    controls.fire_just_pressed = false;
+    controls.hyperspace_just_pressed = false;
+
+    for pressed in touches.iter_just_pressed() {
+        let on_left = pressed.start_position().x < bounds.half_width;
+        if on_left && controls.stick.is_none() {
+            controls.stick = Some(TouchStick {
+                id: pressed.id(),
+                centre: to_world(pressed.start_position()),
+                offset: Vec2::ZERO,
+            });
+        } else if !on_left && controls.trigger.is_none() {
+            // a tap or a flick, told apart when it lifts
+            controls.trigger = Some(pressed.id());
+            controls.trigger_time = 0.0;
+        }
+    }
+
+    controls.stick = controls.stick.and_then(|stick| {
+        let held = touches.get_pressed(stick.id)?;
+        let offset =
+            (to_world(held.position()) - stick.centre).clamp_length_max(touch.stick_radius);
+        Some(TouchStick { offset, ..stick })
+    });
+
+    if let Some(id) = controls.trigger {
+        let tapping = controls.trigger_time < touch.hold_delay;
+
+        if let Some(held) = touches.get_pressed(id) {
+            controls.trigger_time += time.delta_secs();
+            // held too long for a tap, it aims where it is held
+            if tapping && controls.trigger_time >= touch.hold_delay && touch.aim_at_tap {
+                controls.aim = starship.map(|_| to_world(held.position()));
+            }
+        } else {
+            controls.trigger = None;
+
+            if let Some(released) = touches.get_released(id) {
+                let moved = released.distance();
+                // window y grows downwards
+                if -moved.y >= touch.swipe_distance && -moved.y > moved.x.abs() {
+                    controls.hyperspace_just_pressed = true;
+                    controls.aim = None;
+                } else if tapping && touch.aim_at_tap && starship.is_some() {
+                    controls.aim = Some(to_world(released.position()));
+                } else if tapping {
+                    // with nothing to turn, as on the title screen, just fire
+                    controls.fire_just_pressed = true;
+                }
+            }
+        }
+    }
+
+    let mut heading = None;
+    controls.thrust = 0.0;
+    if let Some(stick) = controls.stick {
+        let pushed = stick.offset.length() / touch.stick_radius;
+        if pushed > touch.dead_zone {
+            heading = Some(stick.offset);
+            controls.thrust = past_dead_zone(pushed, touch.thrust_threshold);
+        }
+    }
+
+    controls.rotation = 0.0;
+    let Some((starship, position)) = starship else {
+        controls.aim = None;
+        controls.fire_held = false;
+        return;
+    };
+
+    let aiming = heading.is_none() && controls.aim.is_some();
+    if let Some(wanted) = heading.or(controls.aim.map(|aim| aim - position.0)) {
+        let error = starship.direction().angle_to(wanted);
+        // the most the fixed steps run this frame can turn it
+        let timestep = config.physics.timestep().as_secs_f32();
+        let most = config.physics.starship_rotation_speed * time.delta_secs().max(timestep);
+        controls.rotation = (error / most).clamp(-1.0, 1.0);
+
+        if aiming && error.abs() <= touch.aim_tolerance {
+            controls.fire_just_pressed = true;
+            controls.aim = None;
+        }
+    }
+
+    // holding auto-fires, once any aimed shot is taken
+    controls.fire_held = controls.trigger.is_some()
+        && controls.trigger_time >= touch.hold_delay
+        && controls.aim.is_none();
+}
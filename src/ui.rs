@@ -0,0 +1,149 @@
+use bevy::prelude::*;
+
+use crate::input::{Action, InputBindings, RebindMenu};
+use crate::score::Score;
+use crate::state::{GameState, Lives};
+use crate::wave::Wave;
+
+/// The score and lives readout in the top-left corner.
+#[derive(Component)]
+pub(crate) struct HudText;
+
+/// The "Wave N" banner shown between waves.
+#[derive(Component)]
+pub(crate) struct WaveBanner;
+
+/// The pause overlay, which lists the bindings for rebinding.
+#[derive(Component)]
+pub(crate) struct PauseMenu;
+
+/// A full-screen node with centred text.
+fn overlay(text: impl Into<String>, font_size: f32) -> impl Bundle {
+    (
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        children![(
+            Text::new(text),
+            TextFont {
+                font_size,
+                ..default()
+            },
+            TextLayout::new_with_justify(Justify::Center),
+        )],
+    )
+}
+
+pub(crate) fn spawn_title_screen(mut commands: Commands) {
+    commands.spawn((
+        overlay("ASTEROIDS\n\nPress fire to start", 48.0),
+        DespawnOnExit(GameState::Title),
+    ));
+}
+
+pub(crate) fn spawn_game_over_screen(mut commands: Commands) {
+    commands.spawn((
+        overlay("GAME OVER", 64.0),
+        DespawnOnExit(GameState::GameOver),
+    ));
+}
+
+pub(crate) fn spawn_hud(mut commands: Commands) {
+    commands.spawn((
+        HudText,
+        Text::default(),
+        TextFont {
+            font_size: 28.0,
+            ..default()
+        },
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(12.0),
+            left: Val::Px(16.0),
+            ..default()
+        },
+    ));
+}
+
+pub(crate) fn update_hud(
+    score: Res<Score>,
+    lives: Res<Lives>,
+    mut query: Query<&mut Text, With<HudText>>,
+) {
+    if !score.is_changed() && !lives.is_changed() {
+        return;
+    }
+
+    for mut text in &mut query {
+        text.0 = format!("SCORE {}\nLIVES {}", score.0, lives.0);
+    }
+}
+
+pub(crate) fn update_wave_banner(
+    mut commands: Commands,
+    wave: Res<Wave>,
+    banners: Query<Entity, With<WaveBanner>>,
+) {
+    match (wave.upcoming(), banners.is_empty()) {
+        (Some(number), true) => {
+            commands.spawn((overlay(format!("WAVE {number}"), 56.0), WaveBanner));
+        }
+        (None, false) => {
+            for entity in &banners {
+                commands.entity(entity).despawn();
+            }
+        }
+        _ => {}
+    }
+}
+
+fn pause_menu_text(menu: &RebindMenu, bindings: &InputBindings) -> String {
+    let mut text = String::from("PAUSED\n\n");
+
+    for (index, action) in Action::ALL.into_iter().enumerate() {
+        let cursor = if index == menu.selected { "> " } else { "  " };
+        let keys = if index == menu.selected && menu.listening {
+            "press a key or button...".to_string()
+        } else {
+            bindings.names(action).join(", ")
+        };
+        text += &format!("{cursor}{}  {keys}\n", action.label());
+    }
+
+    text + "\nUp/down to choose, enter or south to rebind"
+}
+
+/// Shows the pause menu while virtual time is paused, redrawn whenever
+/// the selection or bindings change.
+pub(crate) fn update_pause_menu(
+    mut commands: Commands,
+    time: Res<Time<Virtual>>,
+    menu: Res<RebindMenu>,
+    bindings: Res<InputBindings>,
+    menus: Query<Entity, With<PauseMenu>>,
+    children: Query<&Children, With<PauseMenu>>,
+    mut texts: Query<&mut Text>,
+) {
+    match (time.is_paused(), menus.is_empty()) {
+        (true, true) => {
+            commands.spawn((overlay(pause_menu_text(&menu, &bindings), 32.0), PauseMenu));
+        }
+        (true, false) if menu.is_changed() || bindings.is_changed() => {
+            for child in children.iter().flatten() {
+                if let Ok(mut text) = texts.get_mut(*child) {
+                    text.0 = pause_menu_text(&menu, &bindings);
+                }
+            }
+        }
+        (false, false) => {
+            for entity in &menus {
+                commands.entity(entity).despawn();
+            }
+        }
+        _ => {}
+    }
+}
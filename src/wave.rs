@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+
+use crate::components::Asteroid;
+use crate::config::AsteroidsConfig;
+use crate::rng::GameRng;
+use crate::state::NewGame;
+use crate::systems::{spawn_field, ScreenBounds};
+
+/// The current wave, and the countdown to the next one once the field has
+/// been cleared.
+#[derive(Resource, Debug)]
+pub struct Wave {
+    pub number: u32,
+    countdown: Option<Timer>,
+}
+
+impl Wave {
+    pub fn new(number: u32) -> Self {
+        Self {
+            number,
+            countdown: None,
+        }
+    }
+
+    /// The wave about to start, while the "Wave N" interstitial is up.
+    pub fn upcoming(&self) -> Option<u32> {
+        self.countdown.as_ref().map(|_| self.number + 1)
+    }
+}
+
+/// Sent when a wave's asteroids have been spawned.
+#[derive(Message, Clone, Copy, Debug)]
+pub struct WaveStarted {
+    pub number: u32,
+}
+
+/// Starts the interstitial when the last asteroid is gone and spawns the
+/// next wave when it ends.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn advance_wave(
+    time: Res<Time>,
+    mut commands: Commands,
+    mut wave: ResMut<Wave>,
+    mut rng: ResMut<GameRng>,
+    mut started_writer: MessageWriter<WaveStarted>,
+    bounds: Res<ScreenBounds>,
+    config: Res<AsteroidsConfig>,
+    asteroids: Query<(), With<Asteroid>>,
+) {
+    let Some(countdown) = wave.countdown.as_mut() else {
+        if asteroids.is_empty() {
+            wave.countdown = Some(Timer::from_seconds(
+                config.waves.interstitial,
+                TimerMode::Once,
+            ));
+        }
+        return;
+    };
+
+    if !countdown.tick(time.delta()).is_finished() {
+        return;
+    }
+
+    wave.countdown = None;
+    wave.number += 1;
+    spawn_field(&mut commands, &mut rng, &bounds, &config, wave.number);
+    started_writer.write(WaveStarted {
+        number: wave.number,
+    });
+}
+
+pub(crate) fn reset_wave(
+    mut new_game: MessageReader<NewGame>,
+    mut wave: ResMut<Wave>,
+    config: Res<AsteroidsConfig>,
+) {
+    if new_game.read().next().is_some() {
+        *wave = Wave::new(config.waves.starting_wave);
+    }
+}
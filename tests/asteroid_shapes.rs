@@ -0,0 +1,78 @@
+mod common;
+
+use asteroidslib::{
+    headless_app, Asteroid, AsteroidShape, AsteroidSize, AsteroidsConfig, Bullet, Diameter,
+    GameRng, Position, Spin, Velocity, BULLET_SIZE,
+};
+use bevy::prelude::*;
+use common::empty_field;
+
+fn shapes(app: &mut App) -> Vec<AsteroidShape> {
+    let world = app.world_mut();
+    world
+        .query_filtered::<&AsteroidShape, With<Asteroid>>()
+        .iter(world)
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn random_shapes_are_seeded_and_fit_the_diameter() {
+    let first = AsteroidShape::random(&mut GameRng::new(5));
+    assert_eq!(first, AsteroidShape::random(&mut GameRng::new(5)));
+    assert_ne!(first, AsteroidShape::random(&mut GameRng::new(6)));
+
+    assert!(first.0.len() >= 9);
+    assert!(first.0.iter().all(|vertex| vertex.length() <= 0.5));
+}
+
+#[test]
+fn fragments_get_new_shapes() {
+    let mut app = common::app(empty_field().with_seed(11));
+
+    let at = Vec2::new(300.0, 200.0);
+    let parent = AsteroidShape::regular(12);
+    app.world_mut().spawn((
+        Asteroid {
+            size: AsteroidSize::Big,
+        },
+        parent.clone(),
+        Position(at),
+        Velocity(Vec2::ZERO),
+        Diameter(AsteroidSize::Big.scale()),
+    ));
+    app.world_mut().spawn((
+        Bullet,
+        Position(at),
+        Velocity(Vec2::ZERO),
+        Diameter(BULLET_SIZE),
+    ));
+    app.update();
+
+    let fragments = shapes(&mut app);
+    assert_eq!(fragments.len(), 2);
+    assert!(fragments.iter().all(|shape| *shape != parent));
+    assert_ne!(fragments[0], fragments[1]);
+}
+
+#[test]
+fn asteroids_spin_at_their_own_rate() {
+    let mut app = headless_app(AsteroidsConfig::headless().with_seed(9));
+    app.update();
+
+    let spins = |app: &mut App| {
+        let world = app.world_mut();
+        world
+            .query_filtered::<&Spin, With<Asteroid>>()
+            .iter(world)
+            .map(|spin| (spin.angle, spin.rate))
+            .collect::<Vec<_>>()
+    };
+    let before = spins(&mut app);
+    app.update();
+    let after = spins(&mut app);
+
+    for ((angle_before, rate), (angle_after, _)) in before.into_iter().zip(after) {
+        assert!((angle_after - angle_before - rate / 60.0).abs() < 1e-4);
+    }
+}
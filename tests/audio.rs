@@ -0,0 +1,70 @@
+use asteroidslib::{AsteroidSize, AudioConfig, AudioSettings, SaucerSize, SoundEffect};
+
+#[test]
+fn every_effect_is_audible_and_in_range() {
+    let effects = [
+        SoundEffect::Fire,
+        SoundEffect::Thrust,
+        SoundEffect::Explosion(AsteroidSize::Big),
+        SoundEffect::Explosion(AsteroidSize::Medium),
+        SoundEffect::Explosion(AsteroidSize::Small),
+        SoundEffect::Siren(SaucerSize::Large),
+        SoundEffect::Siren(SaucerSize::Small),
+        SoundEffect::Beat { high: false },
+        SoundEffect::Beat { high: true },
+    ];
+
+    for effect in effects {
+        let sound = effect.synthesize();
+        let samples = sound.samples();
+
+        assert!(!samples.is_empty(), "{effect:?}");
+        assert!(
+            samples.iter().all(|s| (-1.0..=1.0).contains(s)),
+            "{effect:?}"
+        );
+        assert!(samples.iter().any(|s| s.abs() > 0.05), "{effect:?}");
+    }
+}
+
+#[test]
+fn synthesis_is_repeatable() {
+    let effect = SoundEffect::Explosion(AsteroidSize::Medium);
+    assert_eq!(effect.synthesize().samples(), effect.synthesize().samples());
+}
+
+#[test]
+fn bigger_asteroids_explode_for_longer() {
+    let length = |size| SoundEffect::Explosion(size).synthesize().duration();
+
+    assert!(length(AsteroidSize::Big) > length(AsteroidSize::Medium));
+    assert!(length(AsteroidSize::Medium) > length(AsteroidSize::Small));
+}
+
+#[test]
+fn heartbeat_quickens_as_the_wave_empties() {
+    let config = AudioConfig::default();
+
+    assert_eq!(config.beat_interval(1.0), config.slowest_beat);
+    assert_eq!(config.beat_interval(0.0), config.fastest_beat);
+    assert!(config.beat_interval(0.3) < config.beat_interval(0.6));
+    // more asteroids than the wave started with never slows it further
+    assert_eq!(config.beat_interval(2.0), config.slowest_beat);
+}
+
+#[test]
+fn mute_and_master_volume_scale_both_buses() {
+    let mut settings = AudioSettings::from(&AudioConfig {
+        master_volume: 0.5,
+        sfx_volume: 0.8,
+        music_volume: 0.4,
+        ..AudioConfig::default()
+    });
+
+    assert_eq!(settings.sfx_gain(), 0.4);
+    assert_eq!(settings.music_gain(), 0.2);
+
+    settings.muted = true;
+    assert_eq!(settings.sfx_gain(), 0.0);
+    assert_eq!(settings.music_gain(), 0.0);
+}
@@ -0,0 +1,190 @@
+mod common;
+
+use asteroidslib::{
+    Bullet, BulletConfig, BulletLifetime, Diameter, Position, Starship, Velocity, BULLET_SIZE,
+    STARSHIP_SIZE,
+};
+use bevy::prelude::*;
+use common::{empty_field, step};
+
+fn app(bullets: BulletConfig) -> App {
+    let mut app = common::app(empty_field().with_seed(4).with_bullets(bullets));
+    app.init_resource::<ButtonInput<KeyCode>>();
+    app
+}
+
+fn bullets() -> BulletConfig {
+    BulletConfig {
+        max_on_screen: 100,
+        cooldown: 0.0,
+        auto_fire: false,
+        auto_fire_interval: 0.25,
+        inherit_ship_velocity: true,
+        lifetime: BulletLifetime::Seconds(10.0),
+    }
+}
+
+/// Presses space for one frame, then releases it.
+fn tap_fire(app: &mut App) {
+    hold_fire(app, 1);
+}
+
+fn hold_fire(app: &mut App, frames: usize) {
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(KeyCode::Space);
+    app.update();
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .clear();
+    step(app, frames - 1);
+
+    let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keys.release(KeyCode::Space);
+    keys.clear();
+}
+
+fn bullet_positions(app: &mut App) -> Vec<Vec2> {
+    let world = app.world_mut();
+    world
+        .query_filtered::<&Position, With<Bullet>>()
+        .iter(world)
+        .map(|position| position.0)
+        .collect()
+}
+
+fn spawn_bullet(app: &mut App, at: Vec2, velocity: Vec2) {
+    app.world_mut().spawn((
+        Bullet,
+        Position(at),
+        Velocity(velocity),
+        Diameter(BULLET_SIZE),
+    ));
+}
+
+#[test]
+fn no_more_than_max_bullets_are_in_flight() {
+    let mut app = app(BulletConfig {
+        max_on_screen: 2,
+        ..bullets()
+    });
+
+    for _ in 0..5 {
+        tap_fire(&mut app);
+        step(&mut app, 1);
+    }
+    assert_eq!(bullet_positions(&mut app).len(), 2);
+}
+
+#[test]
+fn taps_faster_than_the_cooldown_are_dropped() {
+    let mut app = app(BulletConfig {
+        cooldown: 0.5,
+        ..bullets()
+    });
+
+    tap_fire(&mut app);
+    step(&mut app, 2);
+    tap_fire(&mut app);
+    assert_eq!(bullet_positions(&mut app).len(), 1);
+
+    step(&mut app, 30);
+    tap_fire(&mut app);
+    assert_eq!(bullet_positions(&mut app).len(), 2);
+}
+
+#[test]
+fn holding_fire_repeats_only_with_auto_fire() {
+    let mut single = app(bullets());
+    hold_fire(&mut single, 54);
+    assert_eq!(bullet_positions(&mut single).len(), 1);
+
+    let mut repeating = app(BulletConfig {
+        auto_fire: true,
+        ..bullets()
+    });
+    // 0.9 s held: shots at 0, 0.25, 0.5 and 0.75 s.
+    hold_fire(&mut repeating, 54);
+    assert_eq!(bullet_positions(&mut repeating).len(), 4);
+}
+
+#[test]
+fn wrapped_bullets_fly_their_full_distance() {
+    let mut app = app(BulletConfig {
+        lifetime: BulletLifetime::Distance(576.0),
+        ..bullets()
+    });
+    // 1.6 s of flight at this speed, crossing the right edge after 40 units.
+    spawn_bullet(&mut app, Vec2::new(600.0, 200.0), Vec2::new(360.0, 0.0));
+
+    step(&mut app, 60);
+    let positions = bullet_positions(&mut app);
+    assert_eq!(positions.len(), 1);
+    assert!(
+        positions[0].x < 0.0,
+        "bullet should have wrapped: {positions:?}"
+    );
+
+    step(&mut app, 40);
+    assert!(bullet_positions(&mut app).is_empty());
+}
+
+#[test]
+fn bullets_can_expire_after_a_time() {
+    let mut app = app(BulletConfig {
+        lifetime: BulletLifetime::Seconds(0.5),
+        ..bullets()
+    });
+    spawn_bullet(&mut app, Vec2::new(0.0, 200.0), Vec2::new(30.0, 0.0));
+
+    step(&mut app, 25);
+    assert_eq!(bullet_positions(&mut app).len(), 1);
+
+    step(&mut app, 10);
+    assert!(bullet_positions(&mut app).is_empty());
+}
+
+fn fire_while_moving(bullets: BulletConfig) -> (Vec2, Vec2, Vec2) {
+    let mut app = app(bullets);
+    let ship_velocity = Vec2::new(200.0, 300.0);
+    {
+        let world = app.world_mut();
+        let mut velocity = world
+            .query_filtered::<&mut Velocity, With<Starship>>()
+            .single_mut(world)
+            .unwrap();
+        velocity.0 = ship_velocity;
+    }
+    tap_fire(&mut app);
+
+    let world = app.world_mut();
+    let (ship, velocity) = world
+        .query_filtered::<(&Position, &Velocity), With<Starship>>()
+        .single(world)
+        .unwrap();
+    let (ship, ship_velocity) = (ship.0, velocity.0);
+    let (bullet, bullet_velocity) = world
+        .query_filtered::<(&Position, &Velocity), With<Bullet>>()
+        .single(world)
+        .unwrap();
+    (bullet.0 - ship, bullet_velocity.0, ship_velocity)
+}
+
+#[test]
+fn bullets_leave_the_nose_with_the_ship_velocity_added() {
+    let (offset, velocity, ship_velocity) = fire_while_moving(bullets());
+
+    assert!(offset.distance(Vec2::new(0.0, STARSHIP_SIZE / 2.0)) < 1e-3);
+    assert!(velocity.distance(ship_velocity + Vec2::new(0.0, 360.0)) < 1e-3);
+}
+
+#[test]
+fn arcade_bullets_keep_a_fixed_speed() {
+    let (offset, velocity, _) = fire_while_moving(BulletConfig {
+        inherit_ship_velocity: false,
+        ..bullets()
+    });
+
+    assert!(offset.distance(Vec2::new(0.0, STARSHIP_SIZE / 2.0)) < 1e-3);
+    assert!(velocity.distance(Vec2::new(0.0, 360.0)) < 1e-3);
+}
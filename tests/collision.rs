@@ -0,0 +1,147 @@
+mod common;
+
+use asteroidslib::{
+    Asteroid, AsteroidShape, AsteroidSize, Bullet, Collider, Diameter, GameState, Position, Score,
+    ScreenBounds, Spin, Starship, Velocity, BULLET_SIZE, STARSHIP_SIZE,
+};
+use bevy::prelude::*;
+use common::empty_field;
+
+fn starship_at_origin() -> Collider {
+    Collider::starship(
+        &Starship {
+            rotation_angle: 0.0,
+        },
+        &Position(Vec2::ZERO),
+        &Diameter(STARSHIP_SIZE),
+    )
+}
+
+fn small_asteroid(at: Vec2) -> Collider {
+    Collider::asteroid(
+        &AsteroidShape::regular(12),
+        &Spin::default(),
+        &Position(at),
+        &Diameter(AsteroidSize::Small.scale()),
+    )
+}
+
+#[test]
+fn starship_nose_and_wings_follow_the_triangle() {
+    let ship = starship_at_origin();
+
+    // Just touching the nose, well outside the old circle.
+    assert!(ship.overlaps(&small_asteroid(Vec2::new(0.0, 39.0))));
+    // Beside the middle of the hull, inside the old circle but clear of the
+    // triangle.
+    assert!(!ship.overlaps(&small_asteroid(Vec2::new(27.0, 0.0))));
+    // At the wing tip.
+    assert!(ship.overlaps(&small_asteroid(Vec2::new(20.0, -35.0))));
+}
+
+#[test]
+fn rotating_the_starship_rotates_its_outline() {
+    let ship = Collider::starship(
+        &Starship {
+            rotation_angle: -std::f32::consts::FRAC_PI_2,
+        },
+        &Position(Vec2::ZERO),
+        &Diameter(STARSHIP_SIZE),
+    );
+
+    // Nose now points along +x.
+    assert!(ship.overlaps(&small_asteroid(Vec2::new(39.0, 0.0))));
+    assert!(!ship.overlaps(&small_asteroid(Vec2::new(0.0, 39.0))));
+}
+
+#[test]
+fn fast_bullets_cannot_tunnel_through_small_asteroids() {
+    let asteroid = small_asteroid(Vec2::ZERO);
+    let bullet = Collider::bullet(
+        &Position(Vec2::new(30.0, 0.0)),
+        &Velocity(Vec2::new(3600.0, 0.0)),
+        1.0 / 60.0,
+    );
+
+    assert!(bullet.overlaps(&asteroid));
+    assert!(!Collider::circle(Vec2::new(30.0, 0.0), BULLET_SIZE / 2.0).overlaps(&asteroid));
+}
+
+#[test]
+fn swept_bullet_hits_in_the_game() {
+    let mut app = common::app(empty_field());
+
+    let at = Vec2::new(300.0, 200.0);
+    app.world_mut().spawn((
+        Asteroid {
+            size: AsteroidSize::Small,
+        },
+        Position(at),
+        Velocity(Vec2::ZERO),
+        Diameter(AsteroidSize::Small.scale()),
+    ));
+    // One step carries the bullet from one side of the asteroid to the other.
+    let start = at - Vec2::new(30.0, 0.0);
+    app.world_mut().spawn((
+        Bullet,
+        Position(start),
+        Velocity(Vec2::new(3600.0, 0.0)),
+        Diameter(BULLET_SIZE),
+    ));
+    app.update();
+
+    assert_eq!(*app.world().resource::<Score>(), Score(100));
+}
+
+#[test]
+fn distances_wrap_around_the_screen() {
+    let bounds = ScreenBounds {
+        half_width: 640.0,
+        half_height: 360.0,
+    };
+
+    assert_eq!(
+        bounds.wrap(Vec2::new(650.0, -370.0)),
+        Vec2::new(-630.0, 350.0)
+    );
+    assert_eq!(
+        bounds.wrapped_delta(Vec2::new(630.0, 0.0), Vec2::new(-630.0, 0.0)),
+        Vec2::new(20.0, 0.0)
+    );
+
+    let right_edge = small_asteroid(Vec2::new(635.0, 0.0));
+    let left_edge = small_asteroid(Vec2::new(-635.0, 0.0));
+    assert!(!right_edge.overlaps(&left_edge));
+    assert!(right_edge.overlaps_wrapped(&left_edge, &bounds));
+}
+
+#[test]
+fn asteroid_across_an_edge_hits_the_starship_on_the_other_side() {
+    let mut app = common::app(empty_field());
+
+    let bounds = *app.world().resource::<ScreenBounds>();
+    let world = app.world_mut();
+    let (mut starship, mut position) = world
+        .query::<(&mut Starship, &mut Position)>()
+        .single_mut(world)
+        .unwrap();
+    // Nose pointing left, right at the left edge.
+    starship.rotation_angle = std::f32::consts::FRAC_PI_2;
+    position.0 = Vec2::new(-bounds.half_width + 10.0, 0.0);
+
+    world.spawn((
+        Asteroid {
+            size: AsteroidSize::Small,
+        },
+        Position(Vec2::new(bounds.half_width - 10.0, 0.0)),
+        Velocity(Vec2::ZERO),
+        Diameter(AsteroidSize::Small.scale()),
+    ));
+    app.update();
+    app.update();
+
+    assert_eq!(
+        *app.world().resource::<State<GameState>>().get(),
+        GameState::Respawning
+    );
+}
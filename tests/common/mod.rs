@@ -0,0 +1,43 @@
+//! Fixtures shared by the integration tests. Each test file uses only some
+//! of them.
+#![allow(dead_code)]
+
+use asteroidslib::{
+    headless_app, Asteroid, AsteroidSize, AsteroidsConfig, Diameter, Position, Velocity,
+};
+use bevy::prelude::*;
+
+/// A headless game with no asteroids and no waves, for tests to place
+/// exactly what they need in.
+pub fn empty_field() -> AsteroidsConfig {
+    AsteroidsConfig::headless()
+        .without_waves()
+        .with_asteroid_count(0)
+}
+
+/// A headless app playing `config`, stepped once to spawn the starship.
+pub fn app(config: AsteroidsConfig) -> App {
+    let mut app = headless_app(config);
+    app.update();
+    app
+}
+
+pub fn step(app: &mut App, steps: usize) {
+    for _ in 0..steps {
+        app.update();
+    }
+}
+
+/// A big, still asteroid right where the starship spawns.
+pub fn spawn_asteroid_at_centre(app: &mut App) -> Entity {
+    app.world_mut()
+        .spawn((
+            Asteroid {
+                size: AsteroidSize::Big,
+            },
+            Position(Vec2::ZERO),
+            Velocity(Vec2::ZERO),
+            Diameter(AsteroidSize::Big.scale()),
+        ))
+        .id()
+}
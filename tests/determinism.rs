@@ -0,0 +1,36 @@
+use asteroidslib::{headless_app, Asteroid, AsteroidsConfig, GameRng, Position, Velocity};
+use bevy::prelude::*;
+
+fn asteroid_state(seed: u64, steps: usize) -> Vec<(Vec2, Vec2)> {
+    let mut app = headless_app(AsteroidsConfig::headless().with_seed(seed));
+    for _ in 0..=steps {
+        app.update();
+    }
+
+    let world = app.world_mut();
+    let mut state = world
+        .query_filtered::<(&Position, &Velocity), With<Asteroid>>()
+        .iter(world)
+        .map(|(p, v)| (p.0, v.0))
+        .collect::<Vec<_>>();
+    state.sort_by(|a, b| a.0.x.total_cmp(&b.0.x));
+    state
+}
+
+#[test]
+fn same_seed_gives_same_game() {
+    assert_eq!(asteroid_state(42, 120), asteroid_state(42, 120));
+}
+
+#[test]
+fn different_seeds_give_different_fields() {
+    assert_ne!(asteroid_state(1, 0), asteroid_state(2, 0));
+}
+
+#[test]
+fn configured_seed_is_reported() {
+    let mut app = headless_app(AsteroidsConfig::headless().with_seed(7));
+    app.update();
+
+    assert_eq!(app.world().resource::<GameRng>().seed(), 7);
+}
@@ -0,0 +1,198 @@
+mod common;
+
+use asteroidslib::{
+    Action, ActionState, AsteroidsConfig, AsteroidsSet, Bullet, InHyperspace, MobileInputState,
+    Starship, Velocity,
+};
+use bevy::input::touch::{touch_screen_input_system, TouchPhase};
+use bevy::input::InputSystems;
+use bevy::prelude::*;
+use common::empty_field;
+
+#[derive(Clone, Copy, Debug)]
+enum Device {
+    Keyboard,
+    Touch,
+    /// The on-screen buttons.
+    Buttons,
+    Script,
+}
+
+/// Actions for [`press_scripted`] to hold down.
+#[derive(Resource, Default)]
+struct Script(Vec<Action>);
+
+fn press_scripted(script: Res<Script>, mut actions: ResMut<ActionState>) {
+    for action in &script.0 {
+        actions.press(*action);
+    }
+}
+
+/// An 800x600 window with every device wired up. Headless apps leave the
+/// on-screen buttons off, so their state is set here directly.
+fn app() -> App {
+    let mut app = common::app(empty_field().with_window_size(800, 600));
+    app.init_resource::<ButtonInput<KeyCode>>()
+        .init_resource::<Touches>()
+        .init_resource::<MobileInputState>()
+        .init_resource::<Script>()
+        .add_message::<TouchInput>()
+        .add_systems(
+            PreUpdate,
+            (
+                touch_screen_input_system.in_set(InputSystems),
+                press_scripted.after(AsteroidsSet::Input),
+            ),
+        );
+    app
+}
+
+fn touch(app: &mut App, phase: TouchPhase, x: f32, y: f32) {
+    app.world_mut().write_message(TouchInput {
+        phase,
+        position: Vec2::new(x, y),
+        window: Entity::PLACEHOLDER,
+        force: None,
+        id: 1,
+    });
+}
+
+/// Holds thrust, or lets go of it, the way `device` would.
+fn thrust(app: &mut App, device: Device, on: bool) {
+    match device {
+        Device::Keyboard => {
+            let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+            if on {
+                keys.press(KeyCode::ArrowUp);
+            } else {
+                keys.release(KeyCode::ArrowUp);
+            }
+        }
+        // the stick pushed all the way up, the way the starship already faces
+        Device::Touch if on => {
+            touch(app, TouchPhase::Started, 100.0, 300.0);
+            touch(app, TouchPhase::Moved, 100.0, 200.0);
+        }
+        Device::Touch => touch(app, TouchPhase::Ended, 100.0, 200.0),
+        Device::Buttons => {
+            app.world_mut().insert_resource(MobileInputState {
+                up: on,
+                ..default()
+            });
+        }
+        Device::Script => {
+            app.world_mut().resource_mut::<Script>().0 =
+                if on { vec![Action::Thrust] } else { vec![] };
+        }
+    }
+}
+
+/// The starship's velocity after each step of thrusting for `thrusting`
+/// steps, then coasting for `coasting`.
+fn flight(device: Device, thrusting: usize, coasting: usize) -> Vec<Vec2> {
+    let mut app = app();
+    let mut trace = Vec::new();
+    let mut record = |app: &mut App| {
+        let world = app.world_mut();
+        let velocity = world
+            .query_filtered::<&Velocity, With<Starship>>()
+            .single(world)
+            .unwrap();
+        trace.push(velocity.0);
+    };
+
+    thrust(&mut app, device, true);
+    for _ in 0..thrusting {
+        app.update();
+        record(&mut app);
+    }
+    thrust(&mut app, device, false);
+    for _ in 0..coasting {
+        app.update();
+        record(&mut app);
+    }
+
+    trace
+}
+
+#[test]
+fn every_device_flies_the_starship_alike() {
+    let keyboard = flight(Device::Keyboard, 60, 60);
+
+    for device in [Device::Touch, Device::Buttons, Device::Script] {
+        let other = flight(device, 60, 60);
+        assert_eq!(other.len(), keyboard.len());
+        for (step, (a, b)) in keyboard.iter().zip(&other).enumerate() {
+            assert!(
+                a.distance(*b) < 0.01,
+                "{device:?} differs at step {step}: {a} vs {b}"
+            );
+        }
+    }
+}
+
+#[test]
+fn every_device_reaches_top_speed() {
+    let max = AsteroidsConfig::default().physics.starship_max_velocity;
+
+    for device in [
+        Device::Keyboard,
+        Device::Touch,
+        Device::Buttons,
+        Device::Script,
+    ] {
+        let trace = flight(device, 90, 0);
+        let speed = trace.last().unwrap().length();
+        assert!(
+            (speed - max).abs() < 0.01,
+            "{device:?} topped out at {speed}"
+        );
+    }
+}
+
+#[test]
+fn drag_only_slows_a_coasting_starship() {
+    for device in [Device::Buttons, Device::Script] {
+        let trace = flight(device, 30, 30);
+        let (thrusting, coasting) = trace.split_at(30);
+
+        assert!(
+            thrusting.windows(2).all(|pair| pair[1].y > pair[0].y),
+            "{device:?} did not speed up"
+        );
+        assert!(
+            coasting.windows(2).all(|pair| pair[1].y < pair[0].y),
+            "{device:?} did not slow down"
+        );
+    }
+}
+
+#[test]
+fn drag_slows_a_starship_in_hyperspace() {
+    let mut app = app();
+    app.world_mut().resource_mut::<Script>().0 = vec![Action::Hyperspace, Action::Thrust];
+    app.update();
+
+    let world = app.world_mut();
+    let (starship, mut velocity) = world
+        .query_filtered::<(Entity, &mut Velocity), (With<Starship>, With<InHyperspace>)>()
+        .single_mut(world)
+        .unwrap();
+    velocity.0 = Vec2::new(0.0, 100.0);
+    app.update();
+
+    let speed = app.world().get::<Velocity>(starship).unwrap().0.y;
+    assert!(speed > 0.0 && speed < 100.0, "moving at {speed}");
+}
+
+#[test]
+fn scripted_fire_held_down_shoots_once() {
+    let mut app = app();
+    app.world_mut().resource_mut::<Script>().0 = vec![Action::Fire];
+    for _ in 0..10 {
+        app.update();
+    }
+
+    let world = app.world_mut();
+    assert_eq!(world.query::<&Bullet>().iter(world).count(), 1);
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use asteroidslib::{GameState, Lives, NewGame, RulesConfig, Starship};
+use bevy::prelude::*;
+use common::{empty_field, spawn_asteroid_at_centre, step};
+
+fn rules() -> RulesConfig {
+    RulesConfig {
+        starting_lives: 2,
+        respawn_delay: 0.5,
+        respawn_clear_radius: 100.0,
+        game_over_delay: 0.5,
+        ..RulesConfig::default()
+    }
+}
+
+fn app() -> App {
+    common::app(empty_field().with_rules(rules()))
+}
+
+fn state(app: &App) -> GameState {
+    *app.world().resource::<State<GameState>>().get()
+}
+
+fn ship_count(app: &mut App) -> usize {
+    let world = app.world_mut();
+    world.query::<&Starship>().iter(world).count()
+}
+
+#[test]
+fn losing_a_ship_respawns_once_centre_is_clear() {
+    let mut app = app();
+    let asteroid = spawn_asteroid_at_centre(&mut app);
+
+    step(&mut app, 2);
+    assert_eq!(state(&app), GameState::Respawning);
+    assert_eq!(*app.world().resource::<Lives>(), Lives(1));
+    assert_eq!(ship_count(&mut app), 0);
+
+    // The delay has passed, but the asteroid still blocks the centre.
+    step(&mut app, 60);
+    assert_eq!(state(&app), GameState::Respawning);
+
+    app.world_mut().despawn(asteroid);
+    step(&mut app, 2);
+    assert_eq!(state(&app), GameState::Playing);
+    assert_eq!(ship_count(&mut app), 1);
+}
+
+#[test]
+fn last_life_leads_to_game_over_then_title() {
+    let mut app = common::app(empty_field().with_rules(RulesConfig {
+        starting_lives: 1,
+        ..rules()
+    }));
+    spawn_asteroid_at_centre(&mut app);
+
+    step(&mut app, 2);
+    assert_eq!(state(&app), GameState::GameOver);
+
+    step(&mut app, 40);
+    assert_eq!(state(&app), GameState::Title);
+}
+
+#[test]
+fn new_game_restores_lives_and_spawns_a_ship() {
+    let mut app = app();
+    spawn_asteroid_at_centre(&mut app);
+    step(&mut app, 2);
+    assert_eq!(*app.world().resource::<Lives>(), Lives(1));
+
+    app.world_mut().write_message(NewGame);
+    step(&mut app, 40);
+
+    assert_eq!(state(&app), GameState::Playing);
+    assert_eq!(*app.world().resource::<Lives>(), Lives(2));
+    assert_eq!(ship_count(&mut app), 1);
+}
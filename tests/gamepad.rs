@@ -0,0 +1,133 @@
+mod common;
+
+use asteroidslib::{Action, ActionState, Bullet, ShipDestroyed, Starship, Velocity};
+use bevy::input::gamepad::{GamepadConnection, GamepadConnectionEvent, GamepadRumbleRequest};
+use bevy::prelude::*;
+use common::{empty_field, step};
+
+fn app() -> (App, Entity) {
+    let mut app = common::app(empty_field());
+    app.add_message::<GamepadConnectionEvent>()
+        .add_message::<GamepadRumbleRequest>();
+    let gamepad = app.world_mut().spawn(Gamepad::default()).id();
+    (app, gamepad)
+}
+
+fn gamepad(app: &mut App, entity: Entity) -> Mut<'_, Gamepad> {
+    app.world_mut().get_mut::<Gamepad>(entity).unwrap()
+}
+
+fn starship(app: &mut App) -> (f32, Vec2) {
+    let world = app.world_mut();
+    let (starship, velocity) = world
+        .query::<(&Starship, &Velocity)>()
+        .single(world)
+        .unwrap();
+    (starship.rotation_angle, velocity.0)
+}
+
+#[test]
+fn stick_turns_in_proportion_to_how_far_it_is_pushed() {
+    let turn = |x: f32| {
+        let (mut app, pad) = app();
+        gamepad(&mut app, pad)
+            .analog_mut()
+            .set(GamepadAxis::LeftStickX, x);
+        step(&mut app, 10);
+        starship(&mut app).0
+    };
+
+    assert_eq!(turn(0.1), 0.0, "inside the dead zone");
+    assert!(turn(-1.0) > 0.0, "left turns anticlockwise");
+    assert!(turn(1.0) < 0.0);
+
+    let half = turn(0.6);
+    let full = turn(1.0);
+    assert!(half > full && half < 0.0);
+}
+
+#[test]
+fn trigger_thrusts_in_proportion_to_how_far_it_is_pulled() {
+    let speed = |pull: f32| {
+        let (mut app, pad) = app();
+        gamepad(&mut app, pad)
+            .analog_mut()
+            .set(GamepadButton::RightTrigger2, pull);
+        // past Bevy's threshold a real trigger counts as pressed too
+        if pull >= 0.75 {
+            gamepad(&mut app, pad)
+                .digital_mut()
+                .press(GamepadButton::RightTrigger2);
+        }
+        step(&mut app, 10);
+        starship(&mut app).1.y
+    };
+
+    let half = speed(0.5);
+    let most = speed(0.8);
+    let full = speed(1.0);
+    assert!(half > 0.0 && half < most);
+    assert!(most < full, "pressed at {most}, full at {full}");
+}
+
+#[test]
+fn south_button_fires() {
+    let (mut app, pad) = app();
+    gamepad(&mut app, pad)
+        .digital_mut()
+        .press(GamepadButton::South);
+    app.update();
+
+    let world = app.world_mut();
+    assert_eq!(world.query::<&Bullet>().iter(world).count(), 1);
+}
+
+fn unplug(app: &mut App, pad: Entity) {
+    app.world_mut().despawn(pad);
+    app.world_mut().write_message(GamepadConnectionEvent::new(
+        pad,
+        GamepadConnection::Disconnected,
+    ));
+    app.update();
+}
+
+#[test]
+fn unplugging_a_gamepad_pauses_the_game() {
+    let (mut app, pad) = app();
+    unplug(&mut app, pad);
+
+    assert!(app.world().resource::<Time<Virtual>>().is_paused());
+    assert!(app
+        .world()
+        .resource::<ActionState>()
+        .just_pressed(Action::Pause));
+
+    app.update();
+    assert_eq!(
+        *app.world().resource::<ActionState>(),
+        ActionState::default()
+    );
+}
+
+#[test]
+fn unplugging_a_gamepad_while_paused_stays_paused() {
+    let (mut app, pad) = app();
+    app.world_mut().resource_mut::<Time<Virtual>>().pause();
+    unplug(&mut app, pad);
+
+    assert!(app.world().resource::<Time<Virtual>>().is_paused());
+}
+
+#[test]
+fn losing_the_starship_rumbles() {
+    let (mut app, _) = app();
+    app.world_mut().write_message(ShipDestroyed {
+        position: Vec2::ZERO,
+        velocity: Vec2::ZERO,
+        rotation: 0.0,
+    });
+    app.update();
+
+    let requests = app.world().resource::<Messages<GamepadRumbleRequest>>();
+    assert_eq!(requests.len(), 1);
+}
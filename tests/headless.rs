@@ -0,0 +1,74 @@
+use asteroidslib::{headless_app, Asteroid, AsteroidsConfig, Position, Starship};
+use bevy::mesh::Mesh2d;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+
+#[test]
+fn spawns_field_without_render_components() {
+    let mut app = headless_app(AsteroidsConfig::headless().with_asteroid_count(4));
+    app.update();
+
+    let world = app.world_mut();
+    assert_eq!(world.query::<&Starship>().iter(world).count(), 1);
+    assert_eq!(world.query::<&Asteroid>().iter(world).count(), 4);
+    assert_eq!(world.query::<&Mesh2d>().iter(world).count(), 0);
+}
+
+#[test]
+fn asteroids_move_when_stepped() {
+    let mut app = headless_app(AsteroidsConfig::headless().with_asteroid_count(3));
+    app.update();
+
+    let before = asteroid_positions(&mut app);
+    for _ in 0..10 {
+        app.update();
+    }
+    let after = asteroid_positions(&mut app);
+
+    assert!(before.iter().zip(&after).all(|(a, b)| a != b));
+}
+
+fn asteroid_positions(app: &mut App) -> Vec<Vec2> {
+    let world = app.world_mut();
+    world
+        .query_filtered::<&Position, With<Asteroid>>()
+        .iter(world)
+        .map(|p| p.0)
+        .collect()
+}
+
+#[test]
+fn movement_is_independent_of_frame_rate() {
+    let ship_after_one_second = |frames_per_step: u32| {
+        let config = AsteroidsConfig::headless()
+            .with_asteroid_count(0)
+            .without_waves();
+        let frame = config.physics.timestep() * frames_per_step;
+        let steps_per_second = config.physics.timestep_hz as u32;
+
+        let mut app = headless_app(config);
+        let mut keys = ButtonInput::<KeyCode>::default();
+        keys.press(KeyCode::ArrowUp);
+        keys.press(KeyCode::ArrowLeft);
+        app.insert_resource(keys)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(frame));
+
+        // The first update only runs `Startup`.
+        for _ in 0..=steps_per_second / frames_per_step {
+            app.update();
+        }
+
+        let world = app.world_mut();
+        let (starship, position) = world
+            .query::<(&Starship, &Position)>()
+            .single(world)
+            .unwrap();
+        (starship.rotation_angle, position.0)
+    };
+
+    let (angle_60, position_60) = ship_after_one_second(1);
+    let (angle_30, position_30) = ship_after_one_second(2);
+
+    assert!((angle_60 - angle_30).abs() < 1e-4);
+    assert!(position_60.distance(position_30) < 1e-2);
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use asteroidslib::{
+    Asteroid, AsteroidSize, Bullet, Diameter, Position, Score, Velocity, BULLET_SIZE,
+};
+use bevy::prelude::*;
+use common::empty_field;
+
+fn app() -> App {
+    common::app(empty_field().with_seed(5))
+}
+
+fn spawn_asteroid(app: &mut App, size: AsteroidSize, at: Vec2) -> Entity {
+    app.world_mut()
+        .spawn((
+            Asteroid { size },
+            Position(at),
+            Velocity(Vec2::ZERO),
+            Diameter(size.scale()),
+        ))
+        .id()
+}
+
+/// A bullet that ends this step at `at`, having come from the left.
+fn spawn_bullet(app: &mut App, at: Vec2) -> Entity {
+    let velocity = Vec2::new(360.0, 0.0);
+    let start = at - velocity / 60.0;
+    app.world_mut()
+        .spawn((
+            Bullet,
+            Position(start),
+            Velocity(velocity),
+            Diameter(BULLET_SIZE),
+        ))
+        .id()
+}
+
+fn asteroid_sizes(app: &mut App) -> Vec<AsteroidSize> {
+    let world = app.world_mut();
+    world
+        .query::<&Asteroid>()
+        .iter(world)
+        .map(|a| a.size)
+        .collect()
+}
+
+fn bullet_count(app: &mut App) -> usize {
+    let world = app.world_mut();
+    world.query::<&Bullet>().iter(world).count()
+}
+
+#[test]
+fn two_bullets_on_one_asteroid_split_it_once() {
+    let mut app = app();
+    let at = Vec2::new(300.0, 200.0);
+    spawn_asteroid(&mut app, AsteroidSize::Big, at);
+    spawn_bullet(&mut app, at);
+    spawn_bullet(&mut app, at + Vec2::new(0.0, 10.0));
+
+    app.update();
+
+    assert_eq!(
+        asteroid_sizes(&mut app),
+        vec![AsteroidSize::Medium, AsteroidSize::Medium]
+    );
+    assert_eq!(*app.world().resource::<Score>(), Score(20));
+    // The second bullet found nothing left to hit and flies on.
+    assert_eq!(bullet_count(&mut app), 1);
+}
+
+#[test]
+fn one_bullet_breaks_only_the_first_asteroid_in_its_path() {
+    let mut app = app();
+    let at = Vec2::new(300.0, 200.0);
+    // Two small rocks overlapping the bullet's last step, the far one first
+    // in spawn order.
+    let far = spawn_asteroid(&mut app, AsteroidSize::Small, at + Vec2::new(2.0, 0.0));
+    let near = spawn_asteroid(&mut app, AsteroidSize::Small, at - Vec2::new(4.0, 0.0));
+    spawn_bullet(&mut app, at);
+
+    app.update();
+
+    assert!(app.world().get_entity(far).is_ok());
+    assert!(app.world().get_entity(near).is_err());
+    assert_eq!(*app.world().resource::<Score>(), Score(100));
+    assert_eq!(bullet_count(&mut app), 0);
+}
+
+#[test]
+fn simultaneous_hits_on_many_asteroids_resolve_one_each() {
+    let mut app = app();
+    for i in 0..5 {
+        let at = Vec2::new(-400.0 + 200.0 * i as f32, -200.0);
+        spawn_asteroid(&mut app, AsteroidSize::Medium, at);
+        spawn_bullet(&mut app, at);
+        spawn_bullet(&mut app, at);
+    }
+
+    app.update();
+
+    let sizes = asteroid_sizes(&mut app);
+    assert_eq!(sizes.len(), 10);
+    assert!(sizes.iter().all(|size| *size == AsteroidSize::Small));
+    assert_eq!(*app.world().resource::<Score>(), Score(5 * 50));
+    assert_eq!(bullet_count(&mut app), 5);
+}
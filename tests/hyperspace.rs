@@ -0,0 +1,69 @@
+mod common;
+
+use asteroidslib::{GameState, HyperspaceConfig, InHyperspace, Lives, Position, Starship};
+use bevy::prelude::*;
+use common::{empty_field, step};
+
+fn app(failure_chance: f32) -> App {
+    let mut app = common::app(
+        empty_field()
+            .with_seed(3)
+            .with_hyperspace(HyperspaceConfig {
+                duration: 0.5,
+                failure_chance,
+            }),
+    );
+    app.init_resource::<ButtonInput<KeyCode>>();
+    app
+}
+
+/// Presses and releases shift over one frame; without the input plugin
+/// nothing else clears `just_pressed`.
+fn jump(app: &mut App) {
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(KeyCode::ShiftLeft);
+    app.update();
+
+    let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keys.release(KeyCode::ShiftLeft);
+    keys.clear();
+}
+
+fn starship(app: &mut App) -> Option<(Vec2, bool)> {
+    let world = app.world_mut();
+    world
+        .query_filtered::<(&Position, Has<InHyperspace>), With<Starship>>()
+        .single(world)
+        .ok()
+        .map(|(position, in_hyperspace)| (position.0, in_hyperspace))
+}
+
+#[test]
+fn hyperspace_moves_the_starship_and_brings_it_back() {
+    let mut app = app(0.0);
+    assert_eq!(starship(&mut app), Some((Vec2::ZERO, false)));
+
+    jump(&mut app);
+    let (position, in_hyperspace) = starship(&mut app).unwrap();
+    assert!(in_hyperspace);
+    assert_ne!(position, Vec2::ZERO);
+
+    step(&mut app, 40);
+    assert_eq!(starship(&mut app), Some((position, false)));
+}
+
+#[test]
+fn failed_jump_destroys_the_starship() {
+    let mut app = app(1.0);
+
+    jump(&mut app);
+    step(&mut app, 40);
+
+    assert_eq!(starship(&mut app), None);
+    assert_eq!(*app.world().resource::<Lives>(), Lives(2));
+    assert_eq!(
+        *app.world().resource::<State<GameState>>().get(),
+        GameState::Respawning
+    );
+}
@@ -0,0 +1,167 @@
+mod common;
+
+use asteroidslib::{
+    Action, Asteroid, AsteroidSize, BindingsError, Diameter, InputBindings, Position, Starship,
+    Velocity,
+};
+use bevy::prelude::*;
+use common::empty_field;
+
+fn app(bindings: InputBindings) -> App {
+    let mut app = common::app(empty_field().with_bindings(bindings));
+    app.init_resource::<ButtonInput<KeyCode>>();
+    app
+}
+
+/// Presses and releases `key` over one frame; without the input plugin
+/// nothing else clears `just_pressed`.
+fn tap(app: &mut App, key: KeyCode) {
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(key);
+    app.update();
+
+    let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keys.release(key);
+    keys.clear();
+}
+
+fn hold(app: &mut App, key: KeyCode, steps: usize) {
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(key);
+    for _ in 0..steps {
+        app.update();
+    }
+
+    let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+    keys.release(key);
+    keys.clear();
+}
+
+fn starship_velocity(app: &mut App) -> Vec2 {
+    let world = app.world_mut();
+    world
+        .query_filtered::<&Velocity, With<Starship>>()
+        .single(world)
+        .unwrap()
+        .0
+}
+
+#[test]
+fn bindings_file_overrides_listed_actions_only() {
+    let bindings = InputBindings::parse(
+        "# WASD\nthrust = KeyW\nrotate_left = KeyA, ArrowLeft\n\nhyperspace =\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        bindings.keys(Action::Thrust).collect::<Vec<_>>(),
+        [KeyCode::KeyW]
+    );
+    assert_eq!(
+        bindings.keys(Action::RotateLeft).collect::<Vec<_>>(),
+        [KeyCode::KeyA, KeyCode::ArrowLeft]
+    );
+    assert_eq!(bindings.keys(Action::Hyperspace).count(), 0);
+    assert_eq!(
+        bindings.keys(Action::Fire).collect::<Vec<_>>(),
+        [KeyCode::Space]
+    );
+}
+
+#[test]
+fn bindings_survive_a_round_trip() {
+    let mut bindings = InputBindings::default();
+    bindings.bind(Action::Fire, KeyCode::KeyJ);
+
+    assert_eq!(InputBindings::parse(&bindings.to_string()), Ok(bindings));
+}
+
+#[test]
+fn bad_bindings_report_the_line() {
+    assert_eq!(
+        InputBindings::parse("fire = Space\nwarp = KeyW"),
+        Err(BindingsError::UnknownAction(2, "warp".into()))
+    );
+    assert_eq!(
+        InputBindings::parse("fire = Spacebar"),
+        Err(BindingsError::UnknownKey(1, "Spacebar".into()))
+    );
+    assert_eq!(
+        InputBindings::parse("fire Space"),
+        Err(BindingsError::MissingEquals(1))
+    );
+}
+
+#[test]
+fn a_key_drives_one_action_at_a_time() {
+    let mut bindings = InputBindings::default();
+    bindings.add(Action::Thrust, KeyCode::Space);
+
+    assert_eq!(bindings.keys(Action::Fire).count(), 0);
+    assert!(bindings
+        .keys(Action::Thrust)
+        .any(|key| key == KeyCode::Space));
+}
+
+#[test]
+fn rebound_thrust_drives_the_starship() {
+    let mut bindings = InputBindings::default();
+    bindings.bind(Action::Thrust, KeyCode::KeyW);
+    let mut app = app(bindings);
+
+    hold(&mut app, KeyCode::ArrowUp, 10);
+    assert_eq!(starship_velocity(&mut app), Vec2::ZERO);
+
+    hold(&mut app, KeyCode::KeyW, 10);
+    assert!(starship_velocity(&mut app).y > 0.0);
+}
+
+#[test]
+fn pausing_freezes_the_field() {
+    let mut app = app(InputBindings::default());
+    let asteroid = app
+        .world_mut()
+        .spawn((
+            Asteroid {
+                size: AsteroidSize::Small,
+            },
+            Position(Vec2::new(300.0, 0.0)),
+            Velocity(Vec2::new(60.0, 0.0)),
+            Diameter(AsteroidSize::Small.scale()),
+        ))
+        .id();
+    let position = |app: &App| app.world().get::<Position>(asteroid).unwrap().0;
+
+    tap(&mut app, KeyCode::Escape);
+    let paused_at = position(&app);
+    hold(&mut app, KeyCode::Space, 10);
+    assert_eq!(position(&app), paused_at);
+
+    tap(&mut app, KeyCode::KeyP);
+    app.update();
+    assert_ne!(position(&app), paused_at);
+}
+
+#[test]
+fn pause_menu_rebinds_the_selected_action() {
+    let mut app = app(InputBindings::default());
+
+    tap(&mut app, KeyCode::Escape);
+    // rotate left, rotate right, thrust
+    tap(&mut app, KeyCode::ArrowDown);
+    tap(&mut app, KeyCode::ArrowDown);
+    tap(&mut app, KeyCode::Enter);
+    tap(&mut app, KeyCode::KeyW);
+    tap(&mut app, KeyCode::Escape);
+
+    let bindings = app.world().resource::<InputBindings>();
+    assert_eq!(
+        bindings.keys(Action::Thrust).collect::<Vec<_>>(),
+        [KeyCode::KeyW]
+    );
+
+    hold(&mut app, KeyCode::KeyW, 10);
+    assert!(starship_velocity(&mut app).y > 0.0);
+}
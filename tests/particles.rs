@@ -0,0 +1,129 @@
+mod common;
+
+use asteroidslib::{
+    Asteroid, AsteroidSize, Bullet, Diameter, Particle, ParticleConfig, ParticleKind, Position,
+    Velocity, BULLET_SIZE,
+};
+use bevy::prelude::*;
+use common::{empty_field, step};
+
+fn app(particles: ParticleConfig) -> App {
+    let mut app = common::app(empty_field().with_seed(8).with_particles(particles));
+    app.init_resource::<ButtonInput<KeyCode>>();
+    app
+}
+
+fn shoot_asteroid(app: &mut App, size: AsteroidSize, at: Vec2) {
+    let world = app.world_mut();
+    world.spawn((
+        Asteroid { size },
+        Position(at),
+        Velocity(Vec2::ZERO),
+        Diameter(size.scale()),
+    ));
+    world.spawn((
+        Bullet,
+        Position(at),
+        Velocity(Vec2::new(0.0, 360.0)),
+        Diameter(BULLET_SIZE),
+    ));
+    app.update();
+}
+
+/// Live particles of each kind, and how many particle entities exist.
+fn particles(app: &mut App) -> (Vec<ParticleKind>, usize) {
+    let world = app.world_mut();
+    let all: Vec<Particle> = world.query::<&Particle>().iter(world).copied().collect();
+    let alive = all
+        .iter()
+        .filter(|particle| particle.is_alive())
+        .map(|particle| particle.kind)
+        .collect();
+    (alive, all.len())
+}
+
+#[test]
+fn explosions_grow_with_asteroid_size() {
+    let config = ParticleConfig::default();
+    let mut app = app(config);
+
+    let mut counts = Vec::new();
+    for (i, size) in [AsteroidSize::Small, AsteroidSize::Medium, AsteroidSize::Big]
+        .into_iter()
+        .enumerate()
+    {
+        let before = particles(&mut app).0.len();
+        shoot_asteroid(&mut app, size, Vec2::new(-400.0 + 300.0 * i as f32, 200.0));
+        let (alive, _) = particles(&mut app);
+        assert!(alive.iter().all(|kind| *kind == ParticleKind::Spark));
+        counts.push(alive.len() - before);
+    }
+
+    assert_eq!(counts[2], config.explosion_particles);
+    assert!(counts[0] < counts[1] && counts[1] < counts[2], "{counts:?}");
+}
+
+#[test]
+fn burnt_out_particles_are_reused() {
+    let mut app = app(ParticleConfig::default());
+
+    shoot_asteroid(&mut app, AsteroidSize::Small, Vec2::new(300.0, 200.0));
+    let (alive, pooled) = particles(&mut app);
+    assert_eq!(alive.len(), pooled);
+
+    step(&mut app, 60);
+    assert_eq!(particles(&mut app), (Vec::new(), pooled));
+
+    shoot_asteroid(&mut app, AsteroidSize::Small, Vec2::new(-300.0, 200.0));
+    assert_eq!(particles(&mut app).1, pooled);
+}
+
+#[test]
+fn the_pool_never_grows_past_its_size() {
+    let mut app = app(ParticleConfig {
+        max_particles: 10,
+        ..default()
+    });
+
+    shoot_asteroid(&mut app, AsteroidSize::Big, Vec2::new(300.0, 200.0));
+    shoot_asteroid(&mut app, AsteroidSize::Big, Vec2::new(-300.0, 200.0));
+    assert_eq!(particles(&mut app), (vec![ParticleKind::Spark; 10], 10));
+}
+
+#[test]
+fn a_destroyed_starship_breaks_into_three_lines() {
+    let mut app = app(ParticleConfig::default());
+    app.world_mut().spawn((
+        Asteroid {
+            size: AsteroidSize::Big,
+        },
+        Position(Vec2::ZERO),
+        Velocity(Vec2::ZERO),
+        Diameter(AsteroidSize::Big.scale()),
+    ));
+    app.update();
+
+    let debris: Vec<_> = particles(&mut app)
+        .0
+        .into_iter()
+        .filter(|kind| matches!(kind, ParticleKind::Debris { .. }))
+        .collect();
+    assert_eq!(debris.len(), 3);
+}
+
+#[test]
+fn thrusting_leaves_an_exhaust_trail() {
+    let mut app = app(ParticleConfig::default());
+    step(&mut app, 30);
+    assert!(particles(&mut app).0.is_empty());
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(KeyCode::ArrowUp);
+    step(&mut app, 30);
+
+    // Half a second at 40 a second, less those already burnt out.
+    let (alive, _) = particles(&mut app);
+    assert!(!alive.is_empty() && alive.len() <= 20, "{}", alive.len());
+    assert!(alive.iter().all(|kind| *kind == ParticleKind::Exhaust));
+}
@@ -0,0 +1,190 @@
+use asteroidslib::{
+    headless_app, Action, ActionState, Asteroid, AsteroidsConfig, AsteroidsSet,
+    InputRecorderPlugin, InputRecording, InputReplay, InputReplayPlugin, Position, Replay,
+    ReplayError, Score, Starship, Velocity, REPLAY_VERSION,
+};
+use bevy::input::gamepad::{GamepadConnection, GamepadConnectionEvent};
+use bevy::prelude::*;
+
+const FRAMES: u32 = 300;
+
+/// Flies about and shoots, the same way every run.
+fn script(mut frame: Local<u32>, mut actions: ResMut<ActionState>) {
+    *frame += 1;
+    let frame = *frame;
+
+    if frame.is_multiple_of(20) {
+        actions.tap(Action::Fire);
+    }
+    if (30..90).contains(&frame) {
+        actions.press(Action::Thrust);
+    }
+    if (60..120).contains(&frame) {
+        actions.press(Action::RotateLeft);
+    }
+    if frame == 150 {
+        actions.tap(Action::Hyperspace);
+    }
+}
+
+/// Everything the input can change, in a comparable order.
+#[derive(Debug, PartialEq)]
+struct Snapshot {
+    asteroids: Vec<(Vec2, Vec2)>,
+    starship: Option<(Vec2, f32)>,
+    score: u32,
+}
+
+fn snapshot(app: &mut App) -> Snapshot {
+    let world = app.world_mut();
+    let mut asteroids = world
+        .query_filtered::<(&Position, &Velocity), With<Asteroid>>()
+        .iter(world)
+        .map(|(p, v)| (p.0, v.0))
+        .collect::<Vec<_>>();
+    asteroids.sort_by(|a, b| a.0.x.total_cmp(&b.0.x));
+    let starship = world
+        .query::<(&Starship, &Position)>()
+        .single(world)
+        .ok()
+        .map(|(starship, position)| (position.0, starship.rotation_angle));
+    let score = world.resource::<Score>().0;
+
+    Snapshot {
+        asteroids,
+        starship,
+        score,
+    }
+}
+
+fn record(seed: u64) -> (Replay, App) {
+    let mut app = headless_app(AsteroidsConfig::headless().with_seed(seed));
+    app.add_plugins(InputRecorderPlugin::default())
+        .add_systems(PreUpdate, script.after(AsteroidsSet::Input));
+    for _ in 0..FRAMES {
+        app.update();
+    }
+
+    let replay = app.world().resource::<InputRecording>().replay.clone();
+    (replay, app)
+}
+
+fn play(replay: Replay) -> App {
+    let mut app = headless_app(replay.config.clone());
+    let frames = replay.len();
+    app.add_plugins(InputReplayPlugin { replay });
+    for _ in 0..frames {
+        app.update();
+    }
+    app
+}
+
+#[test]
+fn replay_reproduces_the_game() {
+    let (replay, mut recorded) = record(5);
+    assert_eq!(replay.len(), FRAMES as usize);
+    assert_eq!(replay.config.seed, Some(5));
+
+    let saved = replay.to_string();
+    let mut replayed = play(Replay::parse(&saved).unwrap());
+
+    assert!(replayed.world().resource::<InputReplay>().finished());
+    let expected = snapshot(&mut recorded);
+    assert_ne!(
+        expected.starship,
+        Some((Vec2::ZERO, 0.0)),
+        "the script did nothing"
+    );
+    assert_eq!(snapshot(&mut replayed), expected);
+}
+
+/// Unpauses, some time after the gamepad was unplugged.
+fn resume(mut frame: Local<u32>, mut actions: ResMut<ActionState>) {
+    *frame += 1;
+    if *frame == 160 {
+        actions.tap(Action::Pause);
+    }
+}
+
+#[test]
+fn replay_reproduces_a_pause_for_an_unplugged_gamepad() {
+    let mut recorded = headless_app(AsteroidsConfig::headless().with_seed(7));
+    recorded
+        .add_plugins(InputRecorderPlugin::default())
+        .add_message::<GamepadConnectionEvent>()
+        .add_systems(
+            PreUpdate,
+            (script, resume).chain().after(AsteroidsSet::Input),
+        );
+    let pad = recorded.world_mut().spawn(Gamepad::default()).id();
+    for frame in 0..FRAMES {
+        if frame == 100 {
+            recorded.world_mut().despawn(pad);
+            recorded
+                .world_mut()
+                .write_message(GamepadConnectionEvent::new(
+                    pad,
+                    GamepadConnection::Disconnected,
+                ));
+        }
+        recorded.update();
+    }
+
+    let replay = recorded.world().resource::<InputRecording>().replay.clone();
+    let mut replayed = play(Replay::parse(&replay.to_string()).unwrap());
+
+    let elapsed = |app: &App| app.world().resource::<Time<Virtual>>().elapsed();
+    let real = recorded.world().resource::<Time<Real>>().elapsed();
+    assert!(elapsed(&recorded) < real, "the game never paused");
+    assert_eq!(elapsed(&replayed), elapsed(&recorded));
+    assert_eq!(snapshot(&mut replayed), snapshot(&mut recorded));
+}
+
+#[test]
+fn idle_frames_are_stored_once() {
+    let mut app = headless_app(AsteroidsConfig::headless().with_seed(1));
+    app.add_plugins(InputRecorderPlugin::default());
+    for _ in 0..FRAMES {
+        app.update();
+    }
+
+    let replay = &app.world().resource::<InputRecording>().replay;
+    assert_eq!(replay.len(), FRAMES as usize);
+    // the first frame has no time step, every later one the same
+    assert!(replay.to_string().contains(&format!("({},(", FRAMES - 1)));
+}
+
+#[test]
+fn other_versions_are_rejected() {
+    let (replay, _) = record(2);
+    let newer = replay
+        .to_string()
+        .replacen(&format!("version:{REPLAY_VERSION}"), "version:99", 1);
+
+    assert!(matches!(
+        Replay::parse(&newer),
+        Err(ReplayError::Version(99))
+    ));
+    assert!(matches!(
+        Replay::parse("fire = Space"),
+        Err(ReplayError::Format(_))
+    ));
+}
+
+#[test]
+fn recording_is_saved_on_exit() {
+    let path = std::env::temp_dir().join(format!("asteroids-replay-{}.ron", std::process::id()));
+    let mut app = headless_app(AsteroidsConfig::headless().with_seed(3));
+    app.add_plugins(InputRecorderPlugin {
+        path: Some(path.clone()),
+    });
+    for _ in 0..10 {
+        app.update();
+    }
+    app.world_mut().write_message(AppExit::Success);
+    app.update();
+
+    let saved = Replay::load(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(saved.unwrap().len(), 11);
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use asteroidslib::{
+    Bullet, Diameter, GameState, Hostile, Lives, Position, Saucer, SaucerConfig, Score, Starship,
+    Velocity, BULLET_SIZE,
+};
+use bevy::prelude::*;
+use common::{empty_field, step};
+
+fn app() -> App {
+    common::app(empty_field().with_seed(7).with_saucers(SaucerConfig {
+        spawn_interval: 0.1,
+        fire_interval: 100.0,
+        ..SaucerConfig::default()
+    }))
+}
+
+#[test]
+fn shooting_a_saucer_scores_its_points() {
+    let mut app = app();
+    step(&mut app, 10);
+
+    // Bring the saucer on screen, away from the starship.
+    let position = Vec2::new(200.0, 200.0);
+    let world = app.world_mut();
+    let size = world
+        .query::<(&Saucer, &mut Position)>()
+        .single_mut(world)
+        .map(|(saucer, mut saucer_position)| {
+            saucer_position.0 = position;
+            saucer.size
+        })
+        .expect("a saucer should have spawned");
+
+    world.spawn((
+        Bullet,
+        Position(position),
+        Velocity(Vec2::ZERO),
+        Diameter(BULLET_SIZE),
+    ));
+    app.update();
+
+    assert_eq!(*app.world().resource::<Score>(), Score(size.points()));
+    let world = app.world_mut();
+    assert_eq!(world.query::<&Saucer>().iter(world).count(), 0);
+}
+
+#[test]
+fn hostile_bullets_destroy_the_starship_without_scoring() {
+    let mut app = app();
+
+    app.world_mut().spawn((
+        Bullet,
+        Hostile,
+        Position(Vec2::ZERO),
+        Velocity(Vec2::ZERO),
+        Diameter(BULLET_SIZE),
+    ));
+    step(&mut app, 2);
+
+    let world = app.world_mut();
+    assert_eq!(world.query::<&Starship>().iter(world).count(), 0);
+    assert_eq!(*app.world().resource::<Lives>(), Lives(2));
+    assert_eq!(*app.world().resource::<Score>(), Score(0));
+    assert_eq!(
+        *app.world().resource::<State<GameState>>().get(),
+        GameState::Respawning
+    );
+}
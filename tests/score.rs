@@ -0,0 +1,64 @@
+mod common;
+
+use asteroidslib::{
+    Asteroid, AsteroidSize, Bullet, Diameter, Lives, Position, RulesConfig, Score, Velocity,
+    BULLET_SIZE,
+};
+use bevy::prelude::*;
+use common::empty_field;
+
+fn app(rules: RulesConfig) -> App {
+    common::app(empty_field().with_rules(rules))
+}
+
+fn shoot(app: &mut App, size: AsteroidSize, at: Vec2) {
+    let world = app.world_mut();
+    world.spawn((
+        Asteroid { size },
+        Position(at),
+        Velocity(Vec2::ZERO),
+        Diameter(size.scale()),
+    ));
+    world.spawn((
+        Bullet,
+        Position(at),
+        Velocity(Vec2::ZERO),
+        Diameter(BULLET_SIZE),
+    ));
+}
+
+#[test]
+fn points_depend_on_asteroid_size() {
+    let mut app = app(RulesConfig::default());
+
+    shoot(&mut app, AsteroidSize::Big, Vec2::new(300.0, 200.0));
+    app.update();
+    assert_eq!(*app.world().resource::<Score>(), Score(20));
+
+    shoot(&mut app, AsteroidSize::Medium, Vec2::new(-300.0, 200.0));
+    app.update();
+    assert_eq!(*app.world().resource::<Score>(), Score(70));
+
+    shoot(&mut app, AsteroidSize::Small, Vec2::new(-300.0, -200.0));
+    app.update();
+    assert_eq!(*app.world().resource::<Score>(), Score(170));
+}
+
+#[test]
+fn extra_life_every_threshold() {
+    let mut app = app(RulesConfig {
+        starting_lives: 3,
+        extra_life_every: 100,
+        ..RulesConfig::default()
+    });
+
+    // 100 points for a small asteroid crosses the first threshold.
+    shoot(&mut app, AsteroidSize::Small, Vec2::new(300.0, 200.0));
+    app.update();
+    assert_eq!(*app.world().resource::<Lives>(), Lives(4));
+
+    // 20 more points stays below the next one.
+    shoot(&mut app, AsteroidSize::Big, Vec2::new(-300.0, 200.0));
+    app.update();
+    assert_eq!(*app.world().resource::<Lives>(), Lives(4));
+}
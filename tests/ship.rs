@@ -0,0 +1,75 @@
+mod common;
+
+use asteroidslib::{GameState, Invulnerable, RulesConfig, ShipVisualState, Starship};
+use bevy::prelude::*;
+use common::{empty_field, spawn_asteroid_at_centre, step};
+
+fn app() -> App {
+    let mut app = common::app(empty_field().with_rules(RulesConfig {
+        respawn_delay: 0.5,
+        respawn_invulnerability: 1.0,
+        blink_interval: 0.1,
+        ..RulesConfig::default()
+    }));
+    app.init_resource::<ButtonInput<KeyCode>>();
+    app
+}
+
+fn state(app: &App) -> GameState {
+    *app.world().resource::<State<GameState>>().get()
+}
+
+fn starship(app: &mut App) -> Option<(ShipVisualState, bool)> {
+    let world = app.world_mut();
+    world
+        .query_filtered::<(&ShipVisualState, Has<Invulnerable>), With<Starship>>()
+        .single(world)
+        .ok()
+        .map(|(visuals, invulnerable)| (*visuals, invulnerable))
+}
+
+#[test]
+fn thrusting_lights_the_flame() {
+    let mut app = app();
+    assert!(!starship(&mut app).unwrap().0.flame);
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .press(KeyCode::ArrowUp);
+    step(&mut app, 2);
+    assert!(starship(&mut app).unwrap().0.flame);
+
+    app.world_mut()
+        .resource_mut::<ButtonInput<KeyCode>>()
+        .release(KeyCode::ArrowUp);
+    step(&mut app, 2);
+    assert!(!starship(&mut app).unwrap().0.flame);
+}
+
+#[test]
+fn respawned_starship_blinks_and_cannot_be_hit_for_a_while() {
+    let mut app = app();
+    let asteroid = spawn_asteroid_at_centre(&mut app);
+    step(&mut app, 2);
+    assert_eq!(state(&app), GameState::Respawning);
+
+    app.world_mut().despawn(asteroid);
+    step(&mut app, 40);
+    assert_eq!(state(&app), GameState::Playing);
+    assert!(starship(&mut app).unwrap().1);
+
+    // An asteroid on top of the new starship does nothing yet.
+    spawn_asteroid_at_centre(&mut app);
+    let mut seen = Vec::new();
+    for _ in 0..20 {
+        app.update();
+        seen.push(starship(&mut app).unwrap().0.visible);
+    }
+    assert_eq!(state(&app), GameState::Playing);
+    assert!(seen.contains(&true) && seen.contains(&false));
+
+    // Once the protection wears off, the asteroid takes the starship.
+    step(&mut app, 60);
+    assert_eq!(starship(&mut app), None);
+    assert_eq!(state(&app), GameState::Respawning);
+}
@@ -0,0 +1,70 @@
+use asteroidslib::{
+    AsteroidShape, Collider, Diameter, GameRng, Position, ScreenBounds, SpatialGrid, Spin,
+};
+use bevy::prelude::*;
+
+const BOUNDS: ScreenBounds = ScreenBounds {
+    half_width: 640.0,
+    half_height: 360.0,
+};
+
+fn rock(at: Vec2, size: f32) -> Collider {
+    Collider::asteroid(
+        &AsteroidShape::regular(12),
+        &Spin::default(),
+        &Position(at),
+        &Diameter(size),
+    )
+}
+
+#[test]
+fn grid_matches_checking_every_pair() {
+    let mut rng = GameRng::new(21);
+    let mut world = World::new();
+    let mut grid = SpatialGrid::default();
+    grid.reset(&BOUNDS, 100.0);
+
+    let rocks: Vec<(Entity, Collider)> = (0..500)
+        .map(|_| {
+            let collider = rock(rng.random_point(&BOUNDS), rng.range(20.0, 100.0));
+            (world.spawn_empty().id(), collider)
+        })
+        .collect();
+    for (entity, collider) in &rocks {
+        grid.insert(*entity, collider.clone());
+    }
+    assert_eq!(grid.len(), rocks.len());
+
+    for _ in 0..200 {
+        let start = rng.random_point(&BOUNDS);
+        let probe = Collider::segment(start, start + rng.random_point(&BOUNDS) * 0.1);
+
+        let mut found = grid.overlapping(&probe, &BOUNDS);
+        found.sort();
+        let mut expected: Vec<Entity> = rocks
+            .iter()
+            .filter(|(_, collider)| probe.overlaps_wrapped(collider, &BOUNDS))
+            .map(|(entity, _)| *entity)
+            .collect();
+        expected.sort();
+
+        assert_eq!(found, expected);
+    }
+}
+
+#[test]
+fn grid_finds_rocks_across_the_edges() {
+    let mut world = World::new();
+    let mut grid = SpatialGrid::default();
+    grid.reset(&BOUNDS, 100.0);
+
+    let corner = world.spawn_empty().id();
+    grid.insert(corner, rock(Vec2::new(635.0, 355.0), 30.0));
+
+    let probe = Collider::circle(Vec2::new(-635.0, -355.0), 5.0);
+    assert_eq!(grid.overlapping(&probe, &BOUNDS), vec![corner]);
+
+    grid.reset(&BOUNDS, 100.0);
+    assert!(grid.is_empty());
+    assert!(grid.overlapping(&probe, &BOUNDS).is_empty());
+}
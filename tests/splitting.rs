@@ -0,0 +1,104 @@
+mod common;
+
+use asteroidslib::{
+    Asteroid, AsteroidSize, Bullet, Diameter, Position, SplitConfig, Velocity, BULLET_SIZE,
+};
+use bevy::prelude::*;
+use common::empty_field;
+
+fn app(splits: SplitConfig) -> App {
+    common::app(empty_field().with_seed(17).with_splits(splits))
+}
+
+/// Shoots an asteroid moving at `velocity` with a bullet travelling along
+/// `shot`, and returns the fragments' sizes and velocities.
+fn split(
+    app: &mut App,
+    size: AsteroidSize,
+    velocity: Vec2,
+    shot: Vec2,
+) -> Vec<(AsteroidSize, Vec2)> {
+    let at = Vec2::new(300.0, 200.0);
+    let world = app.world_mut();
+    world.spawn((
+        Asteroid { size },
+        Position(at),
+        Velocity(velocity),
+        Diameter(size.scale()),
+    ));
+    world.spawn((Bullet, Position(at), Velocity(shot), Diameter(BULLET_SIZE)));
+    app.update();
+
+    let world = app.world_mut();
+    world
+        .query::<(&Asteroid, &Velocity)>()
+        .iter(world)
+        .map(|(asteroid, velocity)| (asteroid.size, velocity.0))
+        .collect()
+}
+
+fn clear_asteroids(app: &mut App) {
+    let world = app.world_mut();
+    let asteroids: Vec<Entity> = world
+        .query_filtered::<Entity, With<Asteroid>>()
+        .iter(world)
+        .collect();
+    for entity in asteroids {
+        world.despawn(entity);
+    }
+}
+
+#[test]
+fn fragments_carry_on_with_the_parent_velocity() {
+    let mut app = app(SplitConfig::default());
+    let parent = Vec2::new(80.0, -40.0);
+    let shot = Vec2::new(0.0, 360.0);
+
+    let fragments = split(&mut app, AsteroidSize::Big, parent, shot);
+    assert_eq!(fragments.len(), 2);
+
+    let mean = fragments.iter().map(|(_, v)| *v).sum::<Vec2>() / fragments.len() as f32;
+    assert!(mean.distance(parent) < 1e-3);
+
+    for (size, velocity) in fragments {
+        assert_eq!(size, AsteroidSize::Medium);
+        let spread = (velocity - parent).normalize();
+        // Mostly sideways to the shot.
+        assert!(spread.dot(shot.normalize()).abs() < 0.3);
+    }
+}
+
+#[test]
+fn fragment_count_per_size_is_configurable() {
+    let mut app = app(SplitConfig {
+        big_fragments: 3,
+        medium_fragments: 4,
+        ..SplitConfig::default()
+    });
+
+    let fragments = split(&mut app, AsteroidSize::Big, Vec2::ZERO, Vec2::X * 360.0);
+    assert_eq!(fragments.len(), 3);
+    assert!(fragments
+        .iter()
+        .all(|(size, _)| *size == AsteroidSize::Medium));
+
+    clear_asteroids(&mut app);
+
+    let fragments = split(&mut app, AsteroidSize::Medium, Vec2::ZERO, Vec2::X * 360.0);
+    assert_eq!(fragments.len(), 4);
+}
+
+#[test]
+fn smaller_fragments_move_faster() {
+    let mut app = app(SplitConfig::default());
+
+    let speed = |fragments: Vec<(AsteroidSize, Vec2)>| {
+        fragments.iter().map(|(_, v)| v.length()).sum::<f32>() / fragments.len() as f32
+    };
+    let medium = speed(split(&mut app, AsteroidSize::Big, Vec2::ZERO, Vec2::X));
+
+    clear_asteroids(&mut app);
+    let small = speed(split(&mut app, AsteroidSize::Medium, Vec2::ZERO, Vec2::X));
+
+    assert!(small > medium);
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use asteroidslib::{Bullet, InHyperspace, Starship, TouchConfig, TouchControls, Velocity};
+use bevy::input::touch::{touch_screen_input_system, TouchPhase};
+use bevy::input::InputSystems;
+use bevy::prelude::*;
+use common::{empty_field, step};
+
+/// An 800x600 window, so the starship starts under (400, 300).
+fn app(touch: TouchConfig) -> App {
+    let mut app = common::app(empty_field().with_window_size(800, 600).with_touch(touch));
+    app.init_resource::<Touches>()
+        .add_message::<TouchInput>()
+        .add_systems(PreUpdate, touch_screen_input_system.in_set(InputSystems));
+    app
+}
+
+/// Sends one touch event, in window coordinates, and steps once.
+fn touch(app: &mut App, id: u64, phase: TouchPhase, x: f32, y: f32) {
+    app.world_mut().write_message(TouchInput {
+        phase,
+        position: Vec2::new(x, y),
+        window: Entity::PLACEHOLDER,
+        force: None,
+        id,
+    });
+    app.update();
+}
+
+fn starship(app: &mut App) -> (Vec2, Vec2) {
+    let world = app.world_mut();
+    let (starship, velocity) = world
+        .query::<(&Starship, &Velocity)>()
+        .single(world)
+        .unwrap();
+    (starship.direction(), velocity.0)
+}
+
+fn bullets(app: &mut App) -> Vec<Vec2> {
+    let world = app.world_mut();
+    world
+        .query_filtered::<&Velocity, With<Bullet>>()
+        .iter(world)
+        .map(|velocity| velocity.0)
+        .collect()
+}
+
+#[test]
+fn stick_points_the_starship_and_thrusts_when_pushed_far() {
+    let mut app = app(TouchConfig::default());
+
+    // a short push to the right only turns
+    touch(&mut app, 1, TouchPhase::Started, 100.0, 300.0);
+    touch(&mut app, 1, TouchPhase::Moved, 130.0, 300.0);
+    step(&mut app, 30);
+    let (direction, velocity) = starship(&mut app);
+    assert!(direction.x > 0.999, "facing {direction}");
+    assert_eq!(velocity, Vec2::ZERO);
+
+    // pushed to the edge it thrusts that way
+    touch(&mut app, 1, TouchPhase::Moved, 200.0, 300.0);
+    step(&mut app, 10);
+    let (_, velocity) = starship(&mut app);
+    assert!(velocity.x > 0.0 && velocity.y.abs() < 0.01 * velocity.x);
+
+    touch(&mut app, 1, TouchPhase::Ended, 200.0, 300.0);
+    assert!(app.world().resource::<TouchControls>().stick.is_none());
+}
+
+#[test]
+fn tapping_the_right_half_fires() {
+    let mut app = app(TouchConfig::default());
+
+    touch(&mut app, 1, TouchPhase::Started, 600.0, 300.0);
+    touch(&mut app, 1, TouchPhase::Ended, 600.0, 300.0);
+
+    assert_eq!(bullets(&mut app).len(), 1);
+}
+
+#[test]
+fn holding_the_right_half_fires_once_it_is_not_a_tap() {
+    let mut app = app(TouchConfig::default());
+
+    touch(&mut app, 1, TouchPhase::Started, 600.0, 300.0);
+    step(&mut app, 5);
+    assert!(bullets(&mut app).is_empty(), "it could still be a flick");
+
+    step(&mut app, 20);
+    assert_eq!(bullets(&mut app).len(), 1);
+
+    // lifting a hold is not a tap as well
+    touch(&mut app, 1, TouchPhase::Ended, 600.0, 300.0);
+    assert_eq!(bullets(&mut app).len(), 1);
+}
+
+#[test]
+fn flicking_up_on_the_right_half_jumps_to_hyperspace() {
+    let mut app = app(TouchConfig::default());
+
+    touch(&mut app, 1, TouchPhase::Started, 600.0, 400.0);
+    touch(&mut app, 1, TouchPhase::Moved, 600.0, 300.0);
+    touch(&mut app, 1, TouchPhase::Ended, 600.0, 300.0);
+
+    let world = app.world_mut();
+    assert_eq!(world.query::<&InHyperspace>().iter(world).count(), 1);
+    assert!(bullets(&mut app).is_empty());
+}
+
+#[test]
+fn aimed_tap_turns_the_starship_before_firing() {
+    let mut app = app(TouchConfig {
+        aim_at_tap: true,
+        ..default()
+    });
+
+    // straight behind the starship
+    touch(&mut app, 1, TouchPhase::Started, 400.0, 500.0);
+    touch(&mut app, 1, TouchPhase::Ended, 400.0, 500.0);
+    assert!(bullets(&mut app).is_empty());
+
+    step(&mut app, 60);
+    let (direction, _) = starship(&mut app);
+    assert!(direction.y < -0.99, "facing {direction}");
+
+    let bullets = bullets(&mut app);
+    assert_eq!(bullets.len(), 1);
+    assert!(bullets[0].y < 0.0);
+}
@@ -0,0 +1,82 @@
+use asteroidslib::{headless_app, Asteroid, AsteroidsConfig, Velocity, Wave, WaveConfig};
+use bevy::prelude::*;
+
+fn config() -> AsteroidsConfig {
+    AsteroidsConfig::headless()
+        .with_asteroid_count(4)
+        .with_waves(WaveConfig {
+            extra_asteroids_per_wave: 2,
+            max_asteroids: 7,
+            speed_increase_per_wave: 0.5,
+            interstitial: 0.5,
+            ..WaveConfig::default()
+        })
+}
+
+fn asteroid_speeds(app: &mut App) -> Vec<f32> {
+    let world = app.world_mut();
+    world
+        .query_filtered::<&Velocity, With<Asteroid>>()
+        .iter(world)
+        .map(|v| v.0.length())
+        .collect()
+}
+
+fn clear_field(app: &mut App) {
+    let world = app.world_mut();
+    let asteroids = world
+        .query_filtered::<Entity, With<Asteroid>>()
+        .iter(world)
+        .collect::<Vec<_>>();
+    for entity in asteroids {
+        world.despawn(entity);
+    }
+}
+
+#[test]
+fn cleared_field_starts_next_wave_after_interstitial() {
+    let config = config();
+    let mut app = headless_app(config.clone());
+    app.update();
+    assert_eq!(asteroid_speeds(&mut app).len(), 4);
+
+    clear_field(&mut app);
+    app.update();
+    assert_eq!(app.world().resource::<Wave>().upcoming(), Some(2));
+    assert!(asteroid_speeds(&mut app).is_empty());
+
+    for _ in 0..30 {
+        app.update();
+    }
+
+    let wave = app.world().resource::<Wave>();
+    assert_eq!(wave.number, 2);
+    assert_eq!(wave.upcoming(), None);
+
+    let speeds = asteroid_speeds(&mut app);
+    assert_eq!(speeds.len(), 6);
+    let expected = config.physics.asteroid_velocity * 1.5;
+    assert!(speeds.iter().all(|s| (s - expected).abs() < 1e-3));
+}
+
+#[test]
+fn asteroid_count_is_capped() {
+    let config = config();
+
+    assert_eq!(config.wave_asteroid_count(1), 4);
+    assert_eq!(config.wave_asteroid_count(2), 6);
+    assert_eq!(config.wave_asteroid_count(3), 7);
+    assert_eq!(config.wave_asteroid_count(10), 7);
+}
+
+#[test]
+fn game_can_start_on_a_later_wave() {
+    let mut config = config();
+    config.waves.starting_wave = 2;
+
+    let mut app = headless_app(config);
+    app.update();
+
+    assert_eq!(app.world().resource::<Wave>().number, 2);
+    assert_eq!(asteroid_speeds(&mut app).len(), 6);
+}